@@ -1,20 +1,229 @@
+use std::cmp::Ordering;
+
+use bitvec::field::BitField;
+use bitvec::ptr::{BitRef, Mut};
 use bitvec::slice::BitSlice;
 
-use crate::DimensionMismatch;
+use crate::BitBoardError;
+use crate::bitboarddyn::BitBoardDyn;
+use crate::components;
+use crate::coord::{BoundingBox, Coord, Direction};
+use crate::distance::{self, Metric};
+use crate::draw;
+use crate::fov::{self, FovAlgorithm};
+use crate::life::Rule;
+use crate::morphology::StructuringElement;
+use crate::path::{self, DiagonalMovement, Heuristic, Path};
+use crate::symmetry::Symmetry;
+
+/// Returns an error unless the two boards have the same number of rows and columns.
+pub(crate) fn check_dimensions(a: &impl BitBoard, b: &impl BitBoard) -> Result<(), BitBoardError> {
+    if a.n_rows() != b.n_rows() || a.n_cols() != b.n_cols() {
+        return Err(BitBoardError::DimensionMismatch {
+            expected: (a.n_rows(), a.n_cols()),
+            actual: (b.n_rows(), b.n_cols()),
+        });
+    }
+    Ok(())
+}
+
+/// Returns an error unless `row` is on the board.
+fn check_row(board: &impl BitBoard, row: usize) -> Result<(), BitBoardError> {
+    if row >= board.n_rows() {
+        return Err(BitBoardError::RowOutOfBounds {
+            row,
+            n_rows: board.n_rows(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns an error unless `col` is on the board.
+fn check_col(board: &impl BitBoard, col: usize) -> Result<(), BitBoardError> {
+    if col >= board.n_cols() {
+        return Err(BitBoardError::ColOutOfBounds {
+            col,
+            n_cols: board.n_cols(),
+        });
+    }
+    Ok(())
+}
+
+/// Combines `src` into `dst` one machine word at a time using `op`. Both slices must
+/// have the same length.
+fn zip_words(dst: &mut BitSlice, src: &BitSlice, op: impl Fn(usize, usize) -> usize) {
+    let word = usize::BITS as usize;
+    for start in (0..dst.len()).step_by(word) {
+        let end = (start + word).min(dst.len());
+        let value = op(dst[start..end].load_le(), src[start..end].load_le());
+        dst[start..end].store_le(value);
+    }
+}
+
+/// Moves every bit of a row-major `n_rows` x `n_cols` grid by `d_row` rows and `d_col`
+/// columns in place. The whole slice is shifted at once, and the columns that would
+/// otherwise receive bits wrapped around from the neighbouring row are cleared.
+fn shift_bits(bits: &mut BitSlice, n_rows: usize, n_cols: usize, d_row: isize, d_col: isize) {
+    if d_row.unsigned_abs() >= n_rows || d_col.unsigned_abs() >= n_cols {
+        bits.fill(false);
+        return;
+    }
+
+    // Both magnitudes are smaller than the board, so this cannot overflow.
+    let offset = d_row * n_cols as isize + d_col;
+    if offset > 0 {
+        bits.shift_end(offset.unsigned_abs());
+    } else {
+        bits.shift_start(offset.unsigned_abs());
+    }
+
+    let cleared = if d_col > 0 {
+        0..d_col.unsigned_abs()
+    } else {
+        n_cols - d_col.unsigned_abs()..n_cols
+    };
+    if cleared.is_empty() {
+        return;
+    }
+    for row_start in (0..bits.len()).step_by(n_cols) {
+        bits[row_start + cleared.start..row_start + cleared.end].fill(false);
+    }
+}
+
+/// What happens when a neighbor or a shift goes past an edge of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Boundary {
+    /// Going past an edge falls off the board.
+    #[default]
+    Hard,
+
+    /// Going past an edge comes back in on the opposite side, so the board is a torus.
+    Wrap,
+}
+
+/// Which cells count as the neighbors of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The cells above, below, left, and right, as set by
+    /// [`BitBoard::set_cardinal_neighbors`].
+    Four,
+
+    /// The cardinal cells plus the diagonals, as set by [`BitBoard::set_all_neighbors`].
+    Eight,
+}
+
+impl Connectivity {
+    /// Returns the `(d_row, d_col)` offset of each neighbor.
+    #[must_use]
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (-1, 1),
+                (1, -1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// How [`BitBoard::blit`] combines the cells of the source board with the cells
+/// already under it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitMode {
+    /// Overwrite with the source cells.
+    Copy,
 
-pub trait BitBoard: Sized {
+    /// Set the cells that are set in either.
+    Or,
+
+    /// Keep only the cells that are set in both.
+    And,
+
+    /// Toggle the cells that are set in the source.
+    Xor,
+}
+
+/// One connected region of set cells, as found by [`BitBoard::connected_components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// The region's cells, cropped to its bounding box, so that [0, 0] here is
+    /// [`min_row`, `min_col`] of the original board. It has the original's
+    /// [`Boundary`].
+    ///
+    /// [`min_row`]: BoundingBox::min_row
+    /// [`min_col`]: BoundingBox::min_col
+    pub cells: BitBoardDyn,
+
+    /// The number of cells in the region.
+    pub size: usize,
+
+    /// The smallest box holding the region. On a wrapping board a region that crosses
+    /// an edge has a box reaching across the whole board.
+    pub bounding_box: BoundingBox,
+}
+
+/// Returns the part of `0..len` covered by `len_src` cells starting at `start`, and the
+/// offset into the source of its first cell.
+fn clip_span(start: isize, len_src: usize, len: usize) -> (std::ops::Range<usize>, usize) {
+    let skipped = start.min(0).unsigned_abs().min(len_src);
+    let first = start.max(0).unsigned_abs().min(len);
+    let last = start
+        .saturating_add_unsigned(len_src)
+        .clamp(0, len as isize)
+        .unsigned_abs()
+        .max(first);
+    (first..last, skipped)
+}
+
+/// Returns `value + delta` wrapped into `0..len`, for a `value` already in `0..len`.
+fn wrap_add(value: usize, delta: isize, len: usize) -> usize {
+    (value + delta.rem_euclid(len as isize) as usize) % len
+}
+
+pub trait BitBoard: Sized + Clone {
     /// Returns the number of rows in the board.
     fn n_rows(&self) -> usize;
 
     /// Returns the number of columns in the board.
     fn n_cols(&self) -> usize;
 
-    /// Returns a mutable reference to the underlying bits.
+    /// Returns how the board treats its edges.
+    fn boundary(&self) -> Boundary;
+
+    /// Change how the board treats its edges.
+    fn set_boundary(&mut self, boundary: Boundary);
+
+    /// Returns the board with its edges treated according to `boundary`.
+    #[must_use]
+    fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.set_boundary(boundary);
+        self
+    }
+
+    /// Returns a mutable reference to the underlying bits. The slice covers exactly
+    /// the `n_rows * n_cols` cells of the board, in row-major order.
     fn board_mut(&mut self) -> &mut BitSlice;
 
-    /// Returns an immutable reference to the underlying bits.
+    /// Returns an immutable reference to the underlying bits. The slice covers exactly
+    /// the `n_rows * n_cols` cells of the board, in row-major order.
     fn board(&self) -> &BitSlice;
 
+    /// Returns an empty board of `n_rows` x `n_cols`, of the same type and with the
+    /// same [`Boundary`] as this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_rows` or `n_cols` is 0, or if the board does not fit in the
+    /// storage of this type.
+    #[must_use]
+    fn empty_with_dims(&self, n_rows: usize, n_cols: usize) -> Self;
+
     /// Get the index that we can use to directly access a certain spot on the board
     fn index_of(&self, row: usize, col: usize) -> usize {
         assert!(
@@ -28,6 +237,23 @@ pub trait BitBoard: Sized {
         (row * self.n_cols()) + col
     }
 
+    /// Get the index that we can use to directly access a certain spot on the board.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::OutOfBounds` if `row` or `col` is out of bounds.
+    fn try_index_of(&self, row: usize, col: usize) -> Result<usize, BitBoardError> {
+        if row >= self.n_rows() || col >= self.n_cols() {
+            return Err(BitBoardError::OutOfBounds {
+                row,
+                col,
+                n_rows: self.n_rows(),
+                n_cols: self.n_cols(),
+            });
+        }
+        Ok((row * self.n_cols()) + col)
+    }
+
     /// Get the row and column of the linear index
     fn row_col_of(&self, index: usize) -> (usize, usize) {
         assert!(
@@ -40,6 +266,19 @@ pub trait BitBoard: Sized {
         (row, col)
     }
 
+    /// Get the row and column of the linear index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::IndexOutOfBounds` if `index` is not on the board.
+    fn try_row_col_of(&self, index: usize) -> Result<(usize, usize), BitBoardError> {
+        let len = self.n_rows() * self.n_cols();
+        if index >= len {
+            return Err(BitBoardError::IndexOutOfBounds { index, len });
+        }
+        Ok((index / self.n_cols(), index % self.n_cols()))
+    }
+
     /// Set all bits to the desired value.
     fn fill(&mut self, value: bool) {
         self.board_mut().fill(value);
@@ -50,14 +289,94 @@ pub trait BitBoard: Sized {
     /// # Errors
     ///
     /// This function will return an error if the two boards have different dimensions.
-    fn or(&self, other: &Self) -> Result<Self, DimensionMismatch>;
+    fn or(&self, other: &Self) -> Result<Self, BitBoardError>;
 
     /// Returns a new board with the logical AND of the two boards.
     ///
     /// # Errors
     ///
     /// This function will return an error if the two boards have different dimensions.
-    fn and(&self, other: &Self) -> Result<Self, DimensionMismatch>;
+    fn and(&self, other: &Self) -> Result<Self, BitBoardError>;
+
+    /// Returns a new board with the logical XOR of the two boards.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the two boards have different dimensions.
+    fn xor(&self, other: &Self) -> Result<Self, BitBoardError> {
+        let mut result = self.clone();
+        result.xor_assign(other)?;
+        Ok(result)
+    }
+
+    /// Returns a new board with the cells set in `self` but not in `other`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the two boards have different dimensions.
+    fn and_not(&self, other: &Self) -> Result<Self, BitBoardError> {
+        let mut result = self.clone();
+        result.and_not_assign(other)?;
+        Ok(result)
+    }
+
+    /// Returns a new board with every cell flipped.
+    #[must_use]
+    fn not(&self) -> Self {
+        let mut result = self.clone();
+        result.not_assign();
+        result
+    }
+
+    /// Sets `self` to the logical OR of the two boards, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the two boards have different dimensions.
+    fn or_assign(&mut self, other: &Self) -> Result<(), BitBoardError> {
+        check_dimensions(self, other)?;
+        *self.board_mut() |= other.board();
+        Ok(())
+    }
+
+    /// Sets `self` to the logical AND of the two boards, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the two boards have different dimensions.
+    fn and_assign(&mut self, other: &Self) -> Result<(), BitBoardError> {
+        check_dimensions(self, other)?;
+        *self.board_mut() &= other.board();
+        Ok(())
+    }
+
+    /// Sets `self` to the logical XOR of the two boards, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the two boards have different dimensions.
+    fn xor_assign(&mut self, other: &Self) -> Result<(), BitBoardError> {
+        check_dimensions(self, other)?;
+        *self.board_mut() ^= other.board();
+        Ok(())
+    }
+
+    /// Clears every cell of `self` that is set in `other`, without allocating.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the two boards have different dimensions.
+    fn and_not_assign(&mut self, other: &Self) -> Result<(), BitBoardError> {
+        check_dimensions(self, other)?;
+        zip_words(self.board_mut(), other.board(), |a, b| a & !b);
+        Ok(())
+    }
+
+    /// Flips every cell of the board in place.
+    fn not_assign(&mut self) {
+        // `Not` on a `&mut BitSlice` inverts the cells in place, a word at a time
+        let _ = !self.board_mut();
+    }
 
     /// Set the value at index [row, col] to be the `new_val`.
     fn set(&mut self, row: usize, col: usize, value: bool) {
@@ -65,6 +384,17 @@ pub trait BitBoard: Sized {
         self.board_mut().set(new_ind, value);
     }
 
+    /// Set the value at index [row, col] to be `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::OutOfBounds` if `row` or `col` is out of bounds.
+    fn try_set(&mut self, row: usize, col: usize, value: bool) -> Result<(), BitBoardError> {
+        let new_ind = self.try_index_of(row, col)?;
+        self.board_mut().set(new_ind, value);
+        Ok(())
+    }
+
     /// Get the value at index [row, col].
     ///
     /// # Panics
@@ -75,6 +405,19 @@ pub trait BitBoard: Sized {
         *self.board().get(new_ind).as_deref().unwrap_or(&false)
     }
 
+    /// Get a mutable proxy for the value at index [row, col], so that it can be
+    /// written with `*board.get_mut(row, col) = true` or toggled in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` is out of bounds.
+    fn get_mut(&mut self, row: usize, col: usize) -> BitRef<'_, Mut> {
+        let new_ind = self.index_of(row, col);
+        self.board_mut()
+            .get_mut(new_ind)
+            .expect("index_of only returns indices on the board")
+    }
+
     /// Get the value at index [row, col], returning `None` if out of bounds.
     fn try_get(&self, row: usize, col: usize) -> Option<bool> {
         if row >= self.n_rows() || col >= self.n_cols() {
@@ -92,6 +435,17 @@ pub trait BitBoard: Sized {
         }
     }
 
+    /// Set an entire column to a certain value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::ColOutOfBounds` if `col` is out of bounds.
+    fn try_set_col(&mut self, col: usize, value: bool) -> Result<(), BitBoardError> {
+        check_col(self, col)?;
+        self.set_col(col, value);
+        Ok(())
+    }
+
     /// Get the values in a given col.
     ///
     /// # Panics
@@ -105,6 +459,16 @@ pub trait BitBoard: Sized {
         (0..self.n_rows()).map(move |row| self.get(row, col))
     }
 
+    /// Get the values in a given col.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::ColOutOfBounds` if `col` is out of bounds.
+    fn try_get_col(&self, col: usize) -> Result<impl Iterator<Item = bool>, BitBoardError> {
+        check_col(self, col)?;
+        Ok(self.get_col(col))
+    }
+
     /// Set an entire row to a certain value
     fn set_row(&mut self, row: usize, value: bool) {
         for cidx in 0..self.n_cols() {
@@ -113,6 +477,17 @@ pub trait BitBoard: Sized {
         }
     }
 
+    /// Set an entire row to a certain value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::RowOutOfBounds` if `row` is out of bounds.
+    fn try_set_row(&mut self, row: usize, value: bool) -> Result<(), BitBoardError> {
+        check_row(self, row)?;
+        self.set_row(row, value);
+        Ok(())
+    }
+
     /// Get the values in a given row.
     ///
     /// # Panics
@@ -126,211 +501,2320 @@ pub trait BitBoard: Sized {
         (0..self.n_cols()).map(move |col| self.get(row, col))
     }
 
-    /// Will set the neighbors immediately above, below, left, and right to `value`. If
-    /// the neighbor is out of bounds, nothing will happen
-    fn set_cardinal_neighbors(&mut self, row: usize, col: usize, value: bool) {
-        // Above
-        if row > 0 {
-            self.set(row - 1, col, value);
-        }
+    /// Get the values in a given row.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::RowOutOfBounds` if `row` is out of bounds.
+    fn try_get_row(&self, row: usize) -> Result<impl Iterator<Item = bool>, BitBoardError> {
+        check_row(self, row)?;
+        Ok(self.get_row(row))
+    }
 
-        // Below
-        if row < self.n_rows() - 1 {
-            self.set(row + 1, col, value);
-        }
+    /// Iterate over the (row, col) of every set cell, in row-major order.
+    fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.board()
+            .iter_ones()
+            .map(move |idx| self.row_col_of(idx))
+    }
 
-        // Left
-        if col > 0 {
-            self.set(row, col - 1, value);
-        }
+    /// Iterate over the (row, col) of every unset cell, in row-major order.
+    fn iter_zeros(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.board()
+            .iter_zeros()
+            .map(move |idx| self.row_col_of(idx))
+    }
+
+    /// Returns the number of set cells.
+    fn count_ones(&self) -> usize {
+        self.board().count_ones()
+    }
+
+    /// Returns the number of unset cells.
+    fn count_zeros(&self) -> usize {
+        self.board().count_zeros()
+    }
+
+    /// Returns the (row, col) of the first set cell in row-major order, if any.
+    fn first_one(&self) -> Option<(usize, usize)> {
+        self.board().first_one().map(|idx| self.row_col_of(idx))
+    }
+
+    /// Returns the (row, col) of the last set cell in row-major order, if any.
+    fn last_one(&self) -> Option<(usize, usize)> {
+        self.board().last_one().map(|idx| self.row_col_of(idx))
+    }
+
+    /// Returns true if any cell is set.
+    fn any(&self) -> bool {
+        self.board().any()
+    }
+
+    /// Returns true if every cell is set.
+    fn all(&self) -> bool {
+        self.board().all()
+    }
+
+    /// Returns true if no cell is set.
+    fn none(&self) -> bool {
+        self.board().not_any()
+    }
 
-        // Right
-        if col < self.n_cols() - 1 {
-            self.set(row, col + 1, value);
+    /// Will set the neighbors immediately above, below, left, and right to `value`. If
+    /// the neighbor is out of bounds, nothing will happen, unless the board wraps, in
+    /// which case the neighbor on the opposite edge is set instead. If [row, col] is
+    /// itself out of bounds, nothing is set.
+    fn set_cardinal_neighbors(&mut self, row: usize, col: usize, value: bool) {
+        for (d_row, d_col) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            if let Some((r, c)) = self.neighbor_of(row, col, d_row, d_col) {
+                self.set(r, c, value);
+            }
         }
     }
 
     /// Set just the spots diagonal from the given position to `value`. If
-    /// the neighbor is out of bounds, nothing will happen
+    /// the neighbor is out of bounds, nothing will happen, unless the board wraps, in
+    /// which case the neighbor on the opposite edge is set instead. If [row, col] is
+    /// itself out of bounds, nothing is set.
     fn set_diagonals(&mut self, row: usize, col: usize, value: bool) {
-        // Above left
-        if row > 0 && col > 0 {
-            self.set(row - 1, col - 1, value);
+        for (d_row, d_col) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+            if let Some((r, c)) = self.neighbor_of(row, col, d_row, d_col) {
+                self.set(r, c, value);
+            }
         }
+    }
 
-        // Above right
-        if row > 0 && col < self.n_cols() - 1 {
-            self.set(row - 1, col + 1, value);
+    /// Returns the cell `d_row` rows and `d_col` columns away from [row, col], taking
+    /// the board's [`Boundary`] into account. Returns `None` if the cell is off a hard
+    /// edge, or if [row, col] is not on the board.
+    fn neighbor_of(
+        &self,
+        row: usize,
+        col: usize,
+        d_row: isize,
+        d_col: isize,
+    ) -> Option<(usize, usize)> {
+        if row >= self.n_rows() || col >= self.n_cols() {
+            return None;
         }
-
-        // Below left
-        if row < self.n_rows() - 1 && col > 0 {
-            self.set(row + 1, col - 1, value);
+        match self.boundary() {
+            Boundary::Hard => {
+                let r = row
+                    .checked_add_signed(d_row)
+                    .filter(|&r| r < self.n_rows())?;
+                let c = col
+                    .checked_add_signed(d_col)
+                    .filter(|&c| c < self.n_cols())?;
+                Some((r, c))
+            }
+            Boundary::Wrap => Some((
+                wrap_add(row, d_row, self.n_rows()),
+                wrap_add(col, d_col, self.n_cols()),
+            )),
         }
+    }
 
-        // Below right
-        if row < self.n_rows() - 1 && col < self.n_cols() - 1 {
-            self.set(row + 1, col + 1, value);
-        }
+    /// Returns the cell one step from `coord` in `direction`, taking the board's
+    /// [`Boundary`] into account. Returns `None` if the step goes off a hard edge, or if
+    /// `coord` is not on the board.
+    fn neighbor(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        let (d_row, d_col) = direction.offset();
+        self.neighbor_of(coord.row, coord.col, d_row, d_col)
+            .map(Coord::from)
     }
 
     /// Set the cardinal neighbors and the diagonal neighbors to `value`. If
-    /// the neighbor is out of bounds, nothing will happen
+    /// the neighbor is out of bounds, nothing will happen, unless the board wraps, in
+    /// which case the neighbor on the opposite edge is set instead. If [row, col] is
+    /// itself out of bounds, nothing is set.
     fn set_all_neighbors(&mut self, row: usize, col: usize, value: bool) {
         self.set_cardinal_neighbors(row, col, value);
         self.set_diagonals(row, col, value);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{bitboard::BitBoard, bitboardstatic::BitBoardStatic};
-    use rstest::rstest;
+    /// Returns a new board with every cell moved `d_row` rows down and `d_col` columns
+    /// to the right (negative values move up and to the left). On a hard board, cells
+    /// that move past an edge are dropped; they do not wrap into the next row or column.
+    /// On a wrapping board they come back in on the opposite edge.
+    #[must_use]
+    fn shift_by(&self, d_row: isize, d_col: isize) -> Self {
+        let (n_rows, n_cols) = (self.n_rows(), self.n_cols());
+        let mut result = self.clone();
+        match self.boundary() {
+            Boundary::Hard => shift_bits(result.board_mut(), n_rows, n_cols, d_row, d_col),
+            Boundary::Wrap => {
+                // A rotation is the union of at most four hard shifts: one for the cells
+                // that stay on the board, and one for each edge they can wrap across.
+                result.fill(false);
+                let d_row = d_row.rem_euclid(n_rows as isize);
+                let d_col = d_col.rem_euclid(n_cols as isize);
+                for dr in [d_row, d_row - n_rows as isize] {
+                    for dc in [d_col, d_col - n_cols as isize] {
+                        if dr.unsigned_abs() >= n_rows || dc.unsigned_abs() >= n_cols {
+                            continue;
+                        }
+                        let mut part = self.clone();
+                        shift_bits(part.board_mut(), n_rows, n_cols, dr, dc);
+                        *result.board_mut() |= part.board();
+                    }
+                }
+            }
+        }
+        result
+    }
 
-    #[rstest]
-    #[case(0, 0, 0)]
-    #[case(1, 0, 1)]
-    #[case(2, 1, 0)]
-    #[case(3, 1, 1)]
-    fn index_of_and_row_col_of(#[case] index: usize, #[case] row: usize, #[case] col: usize) {
-        let bb = BitBoardStatic::<1>::new(2, 2);
-        assert_eq!(bb.index_of(row, col), index);
-        assert_eq!(bb.row_col_of(index), (row, col));
+    /// Returns a new board with every cell moved up one row.
+    #[must_use]
+    fn shift_north(&self) -> Self {
+        self.shift_by(-1, 0)
     }
 
-    #[rstest]
-    #[case(0, 0, 0)]
-    #[case(1, 0, 1)]
-    #[case(2, 0, 2)]
-    #[case(3, 1, 0)]
-    #[case(4, 1, 1)]
-    #[case(5, 1, 2)]
-    #[case(6, 2, 0)]
-    #[case(7, 2, 1)]
-    #[case(8, 2, 2)]
-    fn index_of_and_row_col_of_3x3(#[case] index: usize, #[case] row: usize, #[case] col: usize) {
-        let bb = BitBoardStatic::<1>::new(3, 3);
-        assert_eq!(bb.index_of(row, col), index);
-        assert_eq!(bb.row_col_of(index), (row, col));
+    /// Returns a new board with every cell moved down one row.
+    #[must_use]
+    fn shift_south(&self) -> Self {
+        self.shift_by(1, 0)
     }
 
-    #[test]
-    fn index_of_and_row_col_of_2x10() {
-        let bb = BitBoardStatic::<1>::new(2, 10);
-        for index in 0..20 {
-            let (row, col) = bb.row_col_of(index);
-            assert_eq!(bb.index_of(row, col), index);
-        }
+    /// Returns a new board with every cell moved right one column.
+    #[must_use]
+    fn shift_east(&self) -> Self {
+        self.shift_by(0, 1)
     }
 
-    #[test]
-    #[should_panic(expected = "col cannot be greater than n_col")]
-    fn set_col_oob_panics() {
-        let mut bb = BitBoardStatic::<1>::new(3, 3);
-        bb.set_col(5, true);
+    /// Returns a new board with every cell moved left one column.
+    #[must_use]
+    fn shift_west(&self) -> Self {
+        self.shift_by(0, -1)
     }
 
-    #[test]
-    #[should_panic(expected = "row cannot be greater than n_rows")]
-    fn set_row_oob_panics() {
-        let mut bb = BitBoardStatic::<1>::new(3, 3);
-        bb.set_row(5, true);
+    /// Returns a new board with every cell moved up one row and right one column.
+    #[must_use]
+    fn shift_north_east(&self) -> Self {
+        self.shift_by(-1, 1)
     }
 
-    #[test]
-    #[should_panic(expected = "index out of bounds")]
-    fn row_col_of_oob() {
-        let bb = BitBoardStatic::<1>::new(3, 3);
-        bb.row_col_of(9);
+    /// Returns a new board with every cell moved up one row and left one column.
+    #[must_use]
+    fn shift_north_west(&self) -> Self {
+        self.shift_by(-1, -1)
     }
 
-    #[test]
-    #[should_panic(expected = "index out of bounds")]
-    fn row_col_of_way_oob() {
-        let bb = BitBoardStatic::<1>::new(2, 2);
-        bb.row_col_of(100);
+    /// Returns a new board with every cell moved down one row and right one column.
+    #[must_use]
+    fn shift_south_east(&self) -> Self {
+        self.shift_by(1, 1)
     }
 
-    #[rstest]
-    #[case(0, 0, false)]
-    #[case(0, 1, true)]
-    #[case(1, 0, false)]
-    #[case(1, 1, false)]
-    fn get_2x2(#[case] row: usize, #[case] col: usize, #[case] expected: bool) {
-        let mut bb = BitBoardStatic::<1>::new(2, 2);
-        bb.set(0, 1, true);
-        assert_eq!(bb.get(row, col), expected);
+    /// Returns a new board with every cell moved down one row and left one column.
+    #[must_use]
+    fn shift_south_west(&self) -> Self {
+        self.shift_by(1, -1)
     }
 
-    #[test]
-    #[should_panic(expected = "row cannot be greater than n_rows")]
-    fn get_oob_row_panics() {
-        let bb = BitBoardStatic::<1>::new(2, 2);
-        bb.get(2, 0);
+    /// Returns, for every cell, how many of its neighbors are set, as four bit-planes:
+    /// bit `i` of a cell's count is stored in plane `i`. The counts are accumulated a
+    /// machine word at a time by adding shifted copies of the board with half adders.
+    #[must_use]
+    fn neighbor_counts(&self, connectivity: Connectivity) -> [Self; 4] {
+        let mut zero = self.clone();
+        zero.fill(false);
+        let mut planes = [zero.clone(), zero.clone(), zero.clone(), zero];
+
+        for &(d_row, d_col) in connectivity.offsets() {
+            let mut carry = self.shift_by(d_row, d_col);
+            for plane in &mut planes {
+                if carry.board().not_any() {
+                    break;
+                }
+                let mut next_carry = plane.clone();
+                *next_carry.board_mut() &= carry.board();
+                *plane.board_mut() ^= carry.board();
+                carry = next_carry;
+            }
+        }
+        planes
     }
 
-    #[test]
-    #[should_panic(expected = "col cannot be greater than n_col")]
-    fn get_oob_col_panics() {
-        let bb = BitBoardStatic::<1>::new(2, 2);
-        bb.get(0, 2);
+    /// Returns a board of the cells that have exactly `k` neighbors set.
+    #[must_use]
+    fn neighbors_exactly(&self, k: usize, connectivity: Connectivity) -> Self {
+        exactly_from_planes(&self.neighbor_counts(connectivity), k)
     }
 
-    #[rstest]
-    #[case(0, 0, Some(false))]
-    #[case(0, 1, Some(true))]
-    #[case(1, 0, Some(false))]
-    #[case(1, 1, Some(false))]
-    #[case(2, 0, None)]
-    #[case(0, 2, None)]
-    #[case(2, 2, None)]
-    fn try_get_2x2(#[case] row: usize, #[case] col: usize, #[case] expected: Option<bool>) {
-        let mut bb = BitBoardStatic::<1>::new(2, 2);
+    /// Returns a board of the cells that have at least `k` neighbors set.
+    #[must_use]
+    fn neighbors_at_least(&self, k: usize, connectivity: Connectivity) -> Self {
+        let planes = self.neighbor_counts(connectivity);
+        let mut result = self.clone();
+        result.fill(false);
+        for count in k..=connectivity.offsets().len() {
+            *result.board_mut() |= exactly_from_planes(&planes, count).board();
+        }
+        result
+    }
+
+    /// Returns the next generation of a Life-like cellular automaton following `rule`,
+    /// using the board's [`Boundary`] for the cells along the edges.
+    #[must_use]
+    fn step(&self, rule: &Rule) -> Self {
+        let planes = self.neighbor_counts(Connectivity::Eight);
+        let mut born = self.clone();
+        born.fill(false);
+        let mut survived = born.clone();
+        for count in 0..=8 {
+            if rule.births_on(count) {
+                *born.board_mut() |= exactly_from_planes(&planes, count).board();
+            }
+            if rule.survives_on(count) {
+                *survived.board_mut() |= exactly_from_planes(&planes, count).board();
+            }
+        }
+
+        zip_words(born.board_mut(), self.board(), |b, alive| b & !alive);
+        *survived.board_mut() &= self.board();
+        *born.board_mut() |= survived.board();
+        born
+    }
+
+    /// Returns a board of the cells connected to the seed at [row, col] through cells
+    /// with the same value as the seed, including the seed itself. If the seed is set
+    /// this is its set region; if it is unset this is the unset region around it.
+    /// Neighbors follow `connectivity` and the board's [`Boundary`].
+    ///
+    /// The region is grown a whole board at a time: each step spreads it to its
+    /// neighbors and masks it back to the cells matching the seed, until it stops
+    /// growing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` is out of bounds.
+    #[must_use]
+    fn flood_fill(&self, row: usize, col: usize, connectivity: Connectivity) -> Self {
+        let mut mask = self.clone();
+        if !self.get(row, col) {
+            mask.not_assign();
+        }
+
+        let mut region = self.empty_with_dims(self.n_rows(), self.n_cols());
+        region.set(row, col, true);
+        let mut size = 1;
+        loop {
+            region = grown(&region, connectivity);
+            *region.board_mut() &= mask.board();
+            let next_size = region.count_ones();
+            if next_size == size {
+                return region;
+            }
+            size = next_size;
+        }
+    }
+
+    /// Sets every cell of the region that [`BitBoard::flood_fill`] would return to
+    /// `value`, like the bucket tool of a paint program.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` is out of bounds.
+    fn flood_fill_in_place(
+        &mut self,
+        row: usize,
+        col: usize,
+        connectivity: Connectivity,
+        value: bool,
+    ) {
+        let region = self.flood_fill(row, col, connectivity);
+        if value {
+            *self.board_mut() |= region.board();
+        } else {
+            zip_words(self.board_mut(), region.board(), |a, b| a & !b);
+        }
+    }
+
+    /// Splits the set cells into regions that are connected under `connectivity` and
+    /// the board's [`Boundary`]. Components are ordered by their first cell in
+    /// row-major order.
+    ///
+    /// The board is labeled in one pass: the set cells of each row are split into
+    /// runs, found a word at a time, and runs that touch are joined with a union-find.
+    fn connected_components(&self, connectivity: Connectivity) -> Vec<Component> {
+        components::components(self, connectivity)
+    }
+
+    /// Returns, for each cell in row-major order, the index in
+    /// [`BitBoard::connected_components`] of the component it belongs to, or `None`
+    /// for unset cells. The label for [row, col] is at `index_of(row, col)`.
+    fn component_labels(&self, connectivity: Connectivity) -> Vec<Option<usize>> {
+        components::labels(self, connectivity)
+    }
+
+    /// Returns the board with `element` stamped, by its origin, onto every set cell.
+    /// Parts of the stamp that fall past an edge follow the board's [`Boundary`].
+    #[must_use]
+    fn dilate(&self, element: &StructuringElement) -> Self {
+        let mut result = self.empty_with_dims(self.n_rows(), self.n_cols());
+        for &(d_row, d_col) in element.offsets() {
+            *result.board_mut() |= self.shift_by(d_row, d_col).board();
+        }
+        result
+    }
+
+    /// Returns the cells where `element`, placed with its origin on the cell, covers
+    /// only set cells. On a hard board, cells past the edge count as unset, so an
+    /// element that reaches past the edge never fits.
+    #[must_use]
+    fn erode(&self, element: &StructuringElement) -> Self {
+        let mut result = self.clone();
+        result.fill(true);
+        for &(d_row, d_col) in element.offsets() {
+            *result.board_mut() &= self.shift_by(-d_row, -d_col).board();
+        }
+        result
+    }
+
+    /// Returns the erosion then dilation of the board, which removes set regions
+    /// smaller than `element` and keeps the rest close to their original shape.
+    #[must_use]
+    fn open(&self, element: &StructuringElement) -> Self {
+        self.erode(element).dilate(element)
+    }
+
+    /// Returns the dilation then erosion of the board, which fills unset gaps smaller
+    /// than `element` and keeps the rest close to their original shape.
+    #[must_use]
+    fn close(&self, element: &StructuringElement) -> Self {
+        self.dilate(element).erode(element)
+    }
+
+    /// Returns the cells in the dilation but not the erosion of the board, which
+    /// outlines the edges of the set regions.
+    #[must_use]
+    fn morphological_gradient(&self, element: &StructuringElement) -> Self {
+        let mut result = self.dilate(element);
+        zip_words(result.board_mut(), self.erode(element).board(), |a, b| {
+            a & !b
+        });
+        result
+    }
+
+    /// Returns the cells where every cell of `hit` is set and every cell of `miss` is
+    /// unset, with both elements placed with their origin on the cell. This finds
+    /// every place a pattern appears, such as corners or isolated cells. On a hard
+    /// board, an element that reaches past the edge never matches.
+    #[must_use]
+    fn hit_or_miss(&self, hit: &StructuringElement, miss: &StructuringElement) -> Self {
+        let mut result = self.erode(hit);
+        *result.board_mut() &= self.not().erode(miss).board();
+        result
+    }
+
+    /// Walking only across set cells, returns the fewest steps from `start` to each cell.
+    ///
+    /// The board is a walkable mask, the opposite of the obstacle mask taken by
+    /// [`BitBoard::astar`]. Cells that cannot be reached are `None`. The distances
+    /// are in row-major order, so the distance to [row, col] is at `index_of(row, col)`.
+    /// Steps follow `connectivity` and the board's [`Boundary`]. If `start` is not
+    /// set, nothing can be reached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is out of bounds.
+    fn distance_map(&self, start: Coord, connectivity: Connectivity) -> Vec<Option<u32>> {
+        path::bfs_distances(self, start, connectivity, None)
+    }
+
+    /// Walking only across set cells, returns a path from `start` to `goal` with the
+    /// fewest steps.
+    ///
+    /// The board is a walkable mask, the opposite of the obstacle mask taken by
+    /// [`BitBoard::astar`]. The path includes both ends, and is `None` if there is no
+    /// path. Steps follow `connectivity` and the board's [`Boundary`]; with
+    /// [`Connectivity::Eight`] a diagonal step may pass between two unset cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `goal` is out of bounds.
+    fn shortest_path(
+        &self,
+        start: Coord,
+        goal: Coord,
+        connectivity: Connectivity,
+    ) -> Option<Vec<Coord>> {
+        path::bfs_path(self, start, goal, connectivity)
+    }
+
+    /// Walking only across unset cells, returns a cheapest path from `start` to `goal`
+    /// found by A* search.
+    ///
+    /// The board is an obstacle mask where set cells are blocked, the opposite of the
+    /// walkable mask taken by [`BitBoard::shortest_path`]. The path is `None` if there
+    /// is no path or either end is blocked. Straight steps cost 1
+    /// and diagonal steps, when `diagonal` allows them, cost the square root of 2.
+    /// Steps follow the board's [`Boundary`].
+    ///
+    /// The path is a cheapest one as long as `heuristic` never overestimates the
+    /// remaining cost: any of them without diagonal moves, or [`Heuristic::Octile`]
+    /// or [`Heuristic::Chebyshev`] with them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `goal` is out of bounds.
+    fn astar(
+        &self,
+        start: Coord,
+        goal: Coord,
+        heuristic: Heuristic,
+        diagonal: DiagonalMovement,
+    ) -> Option<Path> {
+        path::astar(self, start, goal, heuristic, diagonal)
+    }
+
+    /// Walking only across unset cells, returns a cheapest path from `start` to `goal`
+    /// found by Jump Point Search.
+    ///
+    /// The board is an obstacle mask where set cells are blocked, as for
+    /// [`BitBoard::astar`]. The path is `None` if there is no path or either end is
+    /// blocked. It has the same cost as the one from [`BitBoard::astar`] with
+    /// [`Heuristic::Octile`] and [`DiagonalMovement::IfNoneBlocked`], but far fewer
+    /// cells are searched on open ground: runs of cells along a row or column are
+    /// skipped a word at a time, and only cells where the path may need to turn are
+    /// added to the search.
+    ///
+    /// On a wrapping board this falls back to [`BitBoard::astar`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `goal` is out of bounds.
+    fn jump_point_search(&self, start: Coord, goal: Coord) -> Option<Path> {
+        path::JumpGrid::new(self).search(start, goal)
+    }
+
+    /// Returns the distance from every cell to the nearest set cell under `metric`, in
+    /// row-major order, so the distance for [row, col] is at `index_of(row, col)`. Set
+    /// cells are 0, and every cell is `u32::MAX` if no cell is set. Distances follow
+    /// the board's [`Boundary`]. For the distance to the nearest unset cell, transform
+    /// [`BitBoard::not`] instead.
+    fn distance_transform(&self, metric: Metric) -> Vec<u32> {
+        match metric {
+            Metric::Manhattan => distance::grown_distances(self, Connectivity::Four),
+            Metric::Chebyshev => distance::grown_distances(self, Connectivity::Eight),
+            Metric::SquaredEuclidean => distance::squared_euclidean_distances(self),
+        }
+    }
+
+    /// Treating the set cells as opaque, returns a board of the cells that can be seen
+    /// from `origin` within `radius` cells, measured as a straight line, using
+    /// `algorithm`. The origin is always visible, and so is every opaque cell that is
+    /// seen, such as the face of a wall. Sight stops at the edges of the board, even on
+    /// a wrapping board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `origin` is out of bounds.
+    #[must_use]
+    fn field_of_view(&self, origin: Coord, radius: usize, algorithm: FovAlgorithm) -> Self {
+        match algorithm {
+            FovAlgorithm::SymmetricShadowcasting => fov::shadowcast(self, origin, radius),
+            FovAlgorithm::RayCasting => {
+                // The loops below may not reach an out of bounds origin, so check it here
+                self.index_of(origin.row, origin.col);
+                let mut visible = self.empty_with_dims(self.n_rows(), self.n_cols());
+                let radius_sq = radius.saturating_mul(radius);
+                // Only the cells within the square around the origin can be in range
+                let span = |center: usize, len: usize| {
+                    center.saturating_sub(radius)
+                        ..center.saturating_add(radius).saturating_add(1).min(len)
+                };
+                for row in span(origin.row, self.n_rows()) {
+                    for col in span(origin.col, self.n_cols()) {
+                        let cell = Coord::new(row, col);
+                        let (d_row, d_col) = (row.abs_diff(origin.row), col.abs_diff(origin.col));
+                        if d_row * d_row + d_col * d_col <= radius_sq
+                            && self.has_line_of_sight(origin, cell)
+                        {
+                            visible.set(row, col, true);
+                        }
+                    }
+                }
+                visible
+            }
+        }
+    }
+
+    /// Treating the set cells as opaque, returns true if none of the cells on the
+    /// Bresenham line between `from` and `to` is set. The two ends themselves may be
+    /// set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is out of bounds.
+    fn has_line_of_sight(&self, from: Coord, to: Coord) -> bool {
+        // Only the cells between the ends are looked at, so check the ends here
+        self.index_of(from.row, from.col);
+        self.index_of(to.row, to.col);
+        let line = draw::bresenham(
+            (from.row as isize, from.col as isize),
+            (to.row as isize, to.col as isize),
+        );
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|&(row, col)| !self.get(row as usize, col as usize))
+    }
+
+    /// Returns the board mirrored across its main diagonal, so that [row, col] moves
+    /// to [col, row] and the number of rows and columns swap.
+    #[must_use]
+    fn transpose(&self) -> Self {
+        transposed(self)
+    }
+
+    /// Returns the board rotated a quarter turn clockwise. The number of rows and
+    /// columns swap.
+    #[must_use]
+    fn rotate_90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    /// Returns the board rotated half a turn.
+    #[must_use]
+    fn rotate_180(&self) -> Self {
+        rotated_180(self)
+    }
+
+    /// Returns the board rotated a quarter turn counter-clockwise. The number of rows
+    /// and columns swap.
+    #[must_use]
+    fn rotate_270(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    /// Returns the board mirrored left to right, so that column 0 becomes the last
+    /// column.
+    #[must_use]
+    fn flip_horizontal(&self) -> Self {
+        flipped_horizontal(self)
+    }
+
+    /// Returns the board mirrored top to bottom, so that row 0 becomes the last row.
+    #[must_use]
+    fn flip_vertical(&self) -> Self {
+        flipped_vertical(self)
+    }
+
+    /// Returns a copy of the `height` x `width` region whose top left cell is at
+    /// [row, col], with the same [`Boundary`] as this board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `height` or `width` is 0, or if the region does not fit on the board.
+    #[must_use]
+    fn sub_board(&self, row: usize, col: usize, height: usize, width: usize) -> BitBoardDyn {
+        match self.try_sub_board(row, col, height, width) {
+            Ok(board) => board,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns a copy of the `height` x `width` region whose top left cell is at
+    /// [row, col], with the same [`Boundary`] as this board.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::ZeroSized` if `height` or `width` is 0, and
+    /// `BitBoardError::RegionOutOfBounds` if the region does not fit on the board.
+    fn try_sub_board(
+        &self,
+        row: usize,
+        col: usize,
+        height: usize,
+        width: usize,
+    ) -> Result<BitBoardDyn, BitBoardError> {
+        if height == 0 || width == 0 {
+            return Err(BitBoardError::ZeroSized {
+                n_rows: height,
+                n_cols: width,
+            });
+        }
+        let fits = |start: usize, len: usize, max: usize| {
+            start.checked_add(len).is_some_and(|end| end <= max)
+        };
+        if !fits(row, height, self.n_rows()) || !fits(col, width, self.n_cols()) {
+            return Err(BitBoardError::RegionOutOfBounds {
+                row,
+                col,
+                height,
+                width,
+                n_rows: self.n_rows(),
+                n_cols: self.n_cols(),
+            });
+        }
+
+        let mut result = BitBoardDyn::new(height, width).with_boundary(self.boundary());
+        for r in 0..height {
+            let src = self.index_of(row + r, col);
+            result.board_mut()[r * width..(r + 1) * width]
+                .copy_from_bitslice(&self.board()[src..src + width]);
+        }
+        Ok(result)
+    }
+
+    /// Writes `src` onto this board with its top left cell at [row, col], combining
+    /// each cell with the one under it according to `mode`. `row` and `col` may be
+    /// negative, and any part of `src` that falls off the board is ignored. Cells
+    /// outside of the region covered by `src` are left alone.
+    fn blit(&mut self, src: &impl BitBoard, row: isize, col: isize, mode: BlitMode) {
+        let (rows, src_row) = clip_span(row, src.n_rows(), self.n_rows());
+        let (cols, src_col) = clip_span(col, src.n_cols(), self.n_cols());
+        let width = cols.len();
+        if width == 0 {
+            return;
+        }
+
+        for (offset, dst_row) in rows.enumerate() {
+            let from = src.index_of(src_row + offset, src_col);
+            let from = &src.board()[from..from + width];
+            let to = self.index_of(dst_row, cols.start);
+            let to = &mut self.board_mut()[to..to + width];
+            match mode {
+                BlitMode::Copy => to.copy_from_bitslice(from),
+                BlitMode::Or => *to |= from,
+                BlitMode::And => *to &= from,
+                BlitMode::Xor => *to ^= from,
+            }
+        }
+    }
+
+    /// Returns the smallest box holding every set cell, or `None` if no cell is set.
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let n_cols = self.n_cols();
+        let min_row = self.board().first_one()? / n_cols;
+        let max_row = self.board().last_one()? / n_cols;
+
+        // OR the rows together, a word at a time, to find the columns in use
+        let mut cols = self.board()[min_row * n_cols..(min_row + 1) * n_cols].to_bitvec();
+        for row in min_row + 1..=max_row {
+            cols |= &self.board()[row * n_cols..(row + 1) * n_cols];
+        }
+        Some(BoundingBox {
+            min_row,
+            min_col: cols.first_one()?,
+            max_row,
+            max_col: cols.last_one()?,
+        })
+    }
+
+    /// Returns a copy of the smallest region holding every set cell, or `None` if no
+    /// cell is set.
+    #[must_use]
+    fn crop(&self) -> Option<BitBoardDyn> {
+        let bbox = self.bounding_box()?;
+        Some(self.sub_board(bbox.min_row, bbox.min_col, bbox.height(), bbox.width()))
+    }
+
+    /// Changes the board to `n_rows` x `n_cols`. Every cell that is still on the board
+    /// keeps its value at the same [row, col], and any new cells are unset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_rows` or `n_cols` is 0, or if the board does not fit in the
+    /// storage of this type.
+    fn resize(&mut self, n_rows: usize, n_cols: usize) {
+        let mut resized = self.empty_with_dims(n_rows, n_cols);
+        resized.blit(self, 0, 0, BlitMode::Copy);
+        *self = resized;
+    }
+
+    /// Grows the board by adding unset rows to the `top` and `bottom`, and unset
+    /// columns to the `left` and `right`. Existing cells move down by `top` rows and
+    /// right by `left` columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grown board does not fit in the storage of this type.
+    fn pad(&mut self, top: usize, bottom: usize, left: usize, right: usize) {
+        let mut padded =
+            self.empty_with_dims(top + self.n_rows() + bottom, left + self.n_cols() + right);
+        padded.blit(self, top as isize, left as isize, BlitMode::Copy);
+        *self = padded;
+    }
+
+    /// Iterate over each distinct board made by rotating or reflecting this one, along
+    /// with the first [`Symmetry`], in the order of [`Symmetry::ALL`], that makes it.
+    /// The identity always comes first.
+    fn symmetries(&self) -> impl Iterator<Item = (Symmetry, Self)> {
+        let mut variants: Vec<(Symmetry, Self)> = Vec::with_capacity(Symmetry::ALL.len());
+        for symmetry in Symmetry::ALL {
+            let variant = symmetry.apply(self);
+            if !variants
+                .iter()
+                .any(|(_, seen)| cmp_cells(seen, &variant).is_eq())
+            {
+                variants.push((symmetry, variant));
+            }
+        }
+        variants.into_iter()
+    }
+
+    /// Returns the smallest of the rotations and reflections of this board, and the
+    /// [`Symmetry`] that makes it, so that boards which are the same shape up to
+    /// symmetry have the same canonical form.
+    ///
+    /// Boards are ordered by their number of rows, then their number of columns, then
+    /// their cells in row-major order with unset before set. If several symmetries
+    /// give the smallest board, the first in [`Symmetry::ALL`] is returned.
+    #[must_use]
+    fn canonical(&self) -> (Self, Symmetry) {
+        let (symmetry, board) = self
+            .symmetries()
+            .reduce(|best, next| {
+                if cmp_cells(&next.1, &best.1).is_lt() {
+                    next
+                } else {
+                    best
+                }
+            })
+            .expect("there is always the identity");
+        (board, symmetry)
+    }
+
+    /// Sets the cells on the Bresenham line from `from` to `to`, both ends included,
+    /// to `value`. Points are `(row, col)` and may be off the board; only the part of
+    /// the line on the board is drawn, even on a wrapping board.
+    fn draw_line(&mut self, from: (isize, isize), to: (isize, isize), value: bool) {
+        let (n_rows, n_cols) = (self.n_rows(), self.n_cols());
+        draw::clipped_line(from, to, n_rows, n_cols, |row, col| {
+            self.set(row, col, value);
+        });
+    }
+
+    /// Sets the outline of the `height` x `width` rectangle whose top left cell is at
+    /// [row, col] to `value`. Only the part on the board is drawn.
+    fn draw_rect(&mut self, row: isize, col: isize, height: usize, width: usize, value: bool) {
+        let (rows, _) = clip_span(row, height, self.n_rows());
+        let (cols, _) = clip_span(col, width, self.n_cols());
+        if rows.is_empty() || cols.is_empty() {
+            return;
+        }
+        // Each edge is only drawn if it was not clipped off
+        let ends_on_board = |start: isize, len: usize, max: usize| {
+            start
+                .checked_add_unsigned(len)
+                .is_some_and(|end| end <= max as isize)
+        };
+        let mut edge_rows = Vec::with_capacity(2);
+        if row >= 0 {
+            edge_rows.push(rows.start);
+        }
+        if ends_on_board(row, height, self.n_rows()) {
+            edge_rows.push(rows.end - 1);
+        }
+        for r in edge_rows {
+            let start = self.index_of(r, cols.start);
+            self.board_mut()[start..start + cols.len()].fill(value);
+        }
+        let mut edge_cols = Vec::with_capacity(2);
+        if col >= 0 {
+            edge_cols.push(cols.start);
+        }
+        if ends_on_board(col, width, self.n_cols()) {
+            edge_cols.push(cols.end - 1);
+        }
+        for c in edge_cols {
+            for r in rows.clone() {
+                self.set(r, c, value);
+            }
+        }
+    }
+
+    /// Sets every cell of the `height` x `width` rectangle whose top left cell is at
+    /// [row, col] to `value`. Only the part on the board is filled.
+    fn fill_rect(&mut self, row: isize, col: isize, height: usize, width: usize, value: bool) {
+        let (rows, _) = clip_span(row, height, self.n_rows());
+        let (cols, _) = clip_span(col, width, self.n_cols());
+        if cols.is_empty() {
+            return;
+        }
+        for r in rows {
+            let start = self.index_of(r, cols.start);
+            self.board_mut()[start..start + cols.len()].fill(value);
+        }
+    }
+
+    /// Sets the outline of the circle of `radius` around `center` to `value`, using the
+    /// midpoint circle algorithm. Only the part on the board is drawn.
+    fn draw_circle(&mut self, center: (isize, isize), radius: usize, value: bool) {
+        self.draw_ellipse(center, radius, radius, value);
+    }
+
+    /// Sets the circle of `radius` around `center` and everything inside it to `value`.
+    /// The edge is the same as the one drawn by [`BitBoard::draw_circle`], and each row
+    /// is filled as one range. Only the part on the board is filled.
+    fn fill_circle(&mut self, center: (isize, isize), radius: usize, value: bool) {
+        let (n_rows, n_cols) = (self.n_rows(), self.n_cols());
+        draw::ellipse_spans(center, radius, radius, n_rows, n_cols, true, |row, cols| {
+            let start = self.index_of(row, cols.start);
+            self.board_mut()[start..start + cols.len()].fill(value);
+        });
+    }
+
+    /// Sets the outline of the ellipse around `center` that reaches `radius_rows` up
+    /// and down and `radius_cols` left and right to `value`, using the midpoint ellipse
+    /// algorithm. With a zero radius, the ellipse is a straight line. Only the part on
+    /// the board is drawn.
+    fn draw_ellipse(
+        &mut self,
+        center: (isize, isize),
+        radius_rows: usize,
+        radius_cols: usize,
+        value: bool,
+    ) {
+        let (n_rows, n_cols) = (self.n_rows(), self.n_cols());
+        draw::ellipse_spans(
+            center,
+            radius_rows,
+            radius_cols,
+            n_rows,
+            n_cols,
+            false,
+            |row, cols| {
+                let start = self.index_of(row, cols.start);
+                self.board_mut()[start..start + cols.len()].fill(value);
+            },
+        );
+    }
+
+    /// Sets the polygon with the given `(row, col)` vertices, its edges, and every cell
+    /// inside it to `value`. The last vertex joins back to the first. Cells are inside
+    /// if their centers are, by the even-odd rule, so a polygon that crosses itself
+    /// leaves holes where it overlaps. Only the part on the board is filled.
+    fn fill_polygon(&mut self, vertices: &[(isize, isize)], value: bool) {
+        draw::fill_polygon_interior(self, vertices, value);
+        for (&from, &to) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+            self.draw_line(from, to, value);
+        }
+    }
+}
+
+/// Orders boards by their dimensions, then by their cells in row-major order.
+fn cmp_cells<B: BitBoard>(a: &B, b: &B) -> Ordering {
+    (a.n_rows(), a.n_cols())
+        .cmp(&(b.n_rows(), b.n_cols()))
+        .then_with(|| a.board().cmp(b.board()))
+}
+
+/// Returns a board of the cells whose count, as stored in the bit-`planes`, equals `k`.
+fn exactly_from_planes<B: BitBoard>(planes: &[B; 4], k: usize) -> B {
+    let mut result = planes[0].clone();
+    result.fill(k < 1 << planes.len());
+    for (bit, plane) in planes.iter().enumerate() {
+        if k & (1 << bit) == 0 {
+            zip_words(result.board_mut(), plane.board(), |a, b| a & !b);
+        } else {
+            *result.board_mut() &= plane.board();
+        }
+    }
+    result
+}
+
+/// Returns `board` with every set cell also setting its neighbors under
+/// `connectivity`, following the board's [`Boundary`].
+pub(crate) fn grown<B: BitBoard>(board: &B, connectivity: Connectivity) -> B {
+    let mut result = board.clone();
+    match connectivity {
+        Connectivity::Four => {
+            for &(d_row, d_col) in connectivity.offsets() {
+                *result.board_mut() |= board.shift_by(d_row, d_col).board();
+            }
+        }
+        Connectivity::Eight => {
+            // The 3x3 square is a 1x3 row swept over 3 rows, so spread sideways first
+            *result.board_mut() |= board.shift_west().board();
+            *result.board_mut() |= board.shift_east().board();
+            let row = result.clone();
+            *result.board_mut() |= row.shift_north().board();
+            *result.board_mut() |= row.shift_south().board();
+        }
+    }
+    result
+}
+
+/// Copies each set cell of `board` to [col, row] of a new board.
+pub(crate) fn transposed<B: BitBoard>(board: &B) -> B {
+    let mut result = board.empty_with_dims(board.n_cols(), board.n_rows());
+    for (row, col) in board.iter_ones() {
+        result.set(col, row, true);
+    }
+    result
+}
+
+/// Reverses the whole row-major slice, which reverses both the rows and the columns.
+pub(crate) fn rotated_180<B: BitBoard>(board: &B) -> B {
+    let mut result = board.clone();
+    result.board_mut().reverse();
+    result
+}
+
+/// Reverses the cells within each row.
+pub(crate) fn flipped_horizontal<B: BitBoard>(board: &B) -> B {
+    let mut result = board.clone();
+    let n_cols = board.n_cols();
+    for start in (0..board.board().len()).step_by(n_cols) {
+        result.board_mut()[start..start + n_cols].reverse();
+    }
+    result
+}
+
+/// Copies each row, a word at a time, into the mirrored row.
+pub(crate) fn flipped_vertical<B: BitBoard>(board: &B) -> B {
+    let mut result = board.clone();
+    let (n_rows, n_cols) = (board.n_rows(), board.n_cols());
+    for row in 0..n_rows {
+        let src = (n_rows - 1 - row) * n_cols;
+        let dst = row * n_cols;
+        result.board_mut()[dst..dst + n_cols].copy_from_bitslice(&board.board()[src..src + n_cols]);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BitBoardError,
+        bitboard::{BitBoard, BlitMode, Boundary, Component, Connectivity},
+        bitboarddyn::BitBoardDyn,
+        bitboardstatic::BitBoardStatic,
+        coord::{BoundingBox, Coord, Direction},
+        morphology::StructuringElement,
+        parse::picture,
+        symmetry::Symmetry,
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0, 0)]
+    #[case(1, 0, 1)]
+    #[case(2, 1, 0)]
+    #[case(3, 1, 1)]
+    fn index_of_and_row_col_of(#[case] index: usize, #[case] row: usize, #[case] col: usize) {
+        let bb = BitBoardStatic::<1>::new(2, 2);
+        assert_eq!(bb.index_of(row, col), index);
+        assert_eq!(bb.row_col_of(index), (row, col));
+    }
+
+    #[rstest]
+    #[case(0, 0, 0)]
+    #[case(1, 0, 1)]
+    #[case(2, 0, 2)]
+    #[case(3, 1, 0)]
+    #[case(4, 1, 1)]
+    #[case(5, 1, 2)]
+    #[case(6, 2, 0)]
+    #[case(7, 2, 1)]
+    #[case(8, 2, 2)]
+    fn index_of_and_row_col_of_3x3(#[case] index: usize, #[case] row: usize, #[case] col: usize) {
+        let bb = BitBoardStatic::<1>::new(3, 3);
+        assert_eq!(bb.index_of(row, col), index);
+        assert_eq!(bb.row_col_of(index), (row, col));
+    }
+
+    #[test]
+    fn index_of_and_row_col_of_2x10() {
+        let bb = BitBoardStatic::<1>::new(2, 10);
+        for index in 0..20 {
+            let (row, col) = bb.row_col_of(index);
+            assert_eq!(bb.index_of(row, col), index);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "col cannot be greater than n_col")]
+    fn set_col_oob_panics() {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set_col(5, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn set_row_oob_panics() {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set_row(5, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn row_col_of_oob() {
+        let bb = BitBoardStatic::<1>::new(3, 3);
+        bb.row_col_of(9);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn row_col_of_way_oob() {
+        let bb = BitBoardStatic::<1>::new(2, 2);
+        bb.row_col_of(100);
+    }
+
+    #[rstest]
+    #[case(0, 0, false)]
+    #[case(0, 1, true)]
+    #[case(1, 0, false)]
+    #[case(1, 1, false)]
+    fn get_2x2(#[case] row: usize, #[case] col: usize, #[case] expected: bool) {
+        let mut bb = BitBoardStatic::<1>::new(2, 2);
+        bb.set(0, 1, true);
+        assert_eq!(bb.get(row, col), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn get_oob_row_panics() {
+        let bb = BitBoardStatic::<1>::new(2, 2);
+        bb.get(2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "col cannot be greater than n_col")]
+    fn get_oob_col_panics() {
+        let bb = BitBoardStatic::<1>::new(2, 2);
+        bb.get(0, 2);
+    }
+
+    #[rstest]
+    #[case(0, 0, Some(false))]
+    #[case(0, 1, Some(true))]
+    #[case(1, 0, Some(false))]
+    #[case(1, 1, Some(false))]
+    #[case(2, 0, None)]
+    #[case(0, 2, None)]
+    #[case(2, 2, None)]
+    fn try_get_2x2(#[case] row: usize, #[case] col: usize, #[case] expected: Option<bool>) {
+        let mut bb = BitBoardStatic::<1>::new(2, 2);
         bb.set(0, 1, true);
         assert_eq!(bb.try_get(row, col), expected);
     }
 
     #[rstest]
-    #[case(0, vec![true, false, true])]
-    #[case(1, vec![false, true, false])]
-    #[case(2, vec![true, true, true])]
-    fn get_row_3x3(#[case] row: usize, #[case] expected: Vec<bool>) {
+    #[case(0, vec![true, false, true])]
+    #[case(1, vec![false, true, false])]
+    #[case(2, vec![true, true, true])]
+    fn get_row_3x3(#[case] row: usize, #[case] expected: Vec<bool>) {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set(0, 0, true);
+        bb.set(0, 2, true);
+        bb.set(1, 1, true);
+        bb.set_row(2, true);
+
+        assert_eq!(bb.get_row(row).collect::<Vec<bool>>(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn get_row_oob_panics() {
+        let bb = BitBoardStatic::<1>::new(3, 3);
+        let _ = bb.get_row(3).collect::<Vec<bool>>();
+    }
+
+    #[rstest]
+    #[case(0, vec![true, false, true])]
+    #[case(1, vec![false, true, true])]
+    #[case(2, vec![true, false, true])]
+    fn get_col_3x3(#[case] col: usize, #[case] expected: Vec<bool>) {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set(0, 0, true);
+        bb.set(0, 2, true);
+        bb.set(1, 1, true);
+        bb.set(2, 0, true);
+        bb.set(2, 1, true);
+        bb.set(2, 2, true);
+
+        assert_eq!(bb.get_col(col).collect::<Vec<bool>>(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "col cannot be greater than n_col")]
+    fn get_col_oob_panics() {
+        let bb = BitBoardStatic::<1>::new(3, 3);
+        let _ = bb.get_col(3).collect::<Vec<bool>>();
+    }
+
+    fn two_boards() -> (BitBoardStatic<1>, BitBoardStatic<1>) {
+        let mut a = BitBoardStatic::<1>::new(3, 3);
+        a.set_row(0, true);
+        a.set(1, 1, true);
+
+        let mut b = BitBoardStatic::<1>::new(3, 3);
+        b.set_col(0, true);
+        b.set(1, 1, true);
+        (a, b)
+    }
+
+    #[test]
+    fn xor_3x3() {
+        let (a, b) = two_boards();
+        let result = a.xor(&b).unwrap();
+        let expected = [
+            [false, true, true],
+            [true, false, false],
+            [true, false, false],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            assert!(result.get_row(row).eq(expected_row.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn and_not_3x3() {
+        let (a, b) = two_boards();
+        let result = a.and_not(&b).unwrap();
+        let expected = [
+            [false, true, true],
+            [false, false, false],
+            [false, false, false],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            assert!(result.get_row(row).eq(expected_row.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn not_3x3() {
+        let (a, _) = two_boards();
+        let result = a.not();
+        let expected = [
+            [false, false, false],
+            [true, false, true],
+            [true, true, true],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            assert!(result.get_row(row).eq(expected_row.iter().copied()));
+        }
+        assert_eq!(result.not(), a);
+    }
+
+    #[test]
+    fn assign_variants_match_allocating_variants() {
+        let (a, b) = two_boards();
+
+        let mut c = a;
+        c.or_assign(&b).unwrap();
+        assert_eq!(c, a.or(&b).unwrap());
+
+        let mut c = a;
+        c.and_assign(&b).unwrap();
+        assert_eq!(c, a.and(&b).unwrap());
+
+        let mut c = a;
+        c.xor_assign(&b).unwrap();
+        assert_eq!(c, a.xor(&b).unwrap());
+
+        let mut c = a;
+        c.and_not_assign(&b).unwrap();
+        assert_eq!(c, a.and_not(&b).unwrap());
+
+        let mut c = a;
+        c.not_assign();
+        assert_eq!(c, a.not());
+    }
+
+    #[test]
+    fn and_not_spans_multiple_words() {
+        let mut a = BitBoardDyn::new(10, 20);
+        a.fill(true);
+        let mut b = BitBoardDyn::new(10, 20);
+        b.set_col(3, true);
+        b.set(9, 19, true);
+
+        let result = a.and_not(&b).unwrap();
+        assert_eq!(result, b.not());
+    }
+
+    #[test]
+    fn de_morgan() {
+        let (a, b) = two_boards();
+        assert_eq!(a.or(&b).unwrap().not(), a.not().and(&b.not()).unwrap());
+        assert_eq!(a.and(&b).unwrap().not(), a.not().or(&b.not()).unwrap());
+    }
+
+    #[rstest]
+    #[case(1, 1, 1, 2)]
+    #[case(2, 1, 1, 2)]
+    #[case(2, 1, 2, 7)]
+    fn bitwise_dimension_mismatch(
+        #[case] b1r: usize,
+        #[case] b1c: usize,
+        #[case] b2r: usize,
+        #[case] b2c: usize,
+    ) {
+        let mut bb1 = BitBoardDyn::new(b1r, b1c);
+        let bb2 = BitBoardDyn::new(b2r, b2c);
+        assert!(bb1.xor(&bb2).is_err());
+        assert!(bb1.and_not(&bb2).is_err());
+        assert!(bb1.or_assign(&bb2).is_err());
+        assert!(bb1.and_assign(&bb2).is_err());
+        assert!(bb1.xor_assign(&bb2).is_err());
+        assert!(bb1.and_not_assign(&bb2).is_err());
+    }
+
+    /// Shift one cell at a time with `get`/`set`, to compare against.
+    fn shift_by_reference(bb: &BitBoardDyn, d_row: isize, d_col: isize) -> BitBoardDyn {
+        let mut result = BitBoardDyn::new(bb.n_rows(), bb.n_cols());
+        for row in 0..bb.n_rows() {
+            for col in 0..bb.n_cols() {
+                let new_row = row.checked_add_signed(d_row);
+                let new_col = col.checked_add_signed(d_col);
+                if let (Some(r), Some(c)) = (new_row, new_col)
+                    && r < bb.n_rows()
+                    && c < bb.n_cols()
+                {
+                    result.set(r, c, bb.get(row, col));
+                }
+            }
+        }
+        result
+    }
+
+    #[rstest]
+    #[case(-1, 0)]
+    #[case(1, 0)]
+    #[case(0, 1)]
+    #[case(0, -1)]
+    #[case(-1, 1)]
+    #[case(-1, -1)]
+    #[case(1, 1)]
+    #[case(1, -1)]
+    fn shift_directions_3x3(#[case] d_row: isize, #[case] d_col: isize) {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set(1, 1, true);
+        let shifted = bb.shift_by(d_row, d_col);
+
+        let mut expected = BitBoardStatic::<1>::new(3, 3);
+        expected.set((1 + d_row) as usize, (1 + d_col) as usize, true);
+        assert_eq!(shifted, expected);
+    }
+
+    #[test]
+    fn named_shifts_match_shift_by() {
+        let mut bb = BitBoardDyn::new(4, 5);
+        bb.set_row(1, true);
+        bb.set_col(3, true);
+
+        assert_eq!(bb.shift_north(), bb.shift_by(-1, 0));
+        assert_eq!(bb.shift_south(), bb.shift_by(1, 0));
+        assert_eq!(bb.shift_east(), bb.shift_by(0, 1));
+        assert_eq!(bb.shift_west(), bb.shift_by(0, -1));
+        assert_eq!(bb.shift_north_east(), bb.shift_by(-1, 1));
+        assert_eq!(bb.shift_north_west(), bb.shift_by(-1, -1));
+        assert_eq!(bb.shift_south_east(), bb.shift_by(1, 1));
+        assert_eq!(bb.shift_south_west(), bb.shift_by(1, -1));
+    }
+
+    #[test]
+    fn shift_east_does_not_wrap_into_next_row() {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set_col(2, true);
+        assert_eq!(bb.shift_east(), BitBoardStatic::<1>::new(3, 3));
+
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.set_col(0, true);
+        assert_eq!(bb.shift_west(), BitBoardStatic::<1>::new(3, 3));
+    }
+
+    #[test]
+    fn shift_past_board_is_empty() {
+        let mut bb = BitBoardDyn::new(3, 4);
+        bb.fill(true);
+        assert!(bb.shift_by(3, 0).board().not_any());
+        assert!(bb.shift_by(0, -4).board().not_any());
+        assert!(bb.shift_by(-10, 10).board().not_any());
+    }
+
+    #[test]
+    fn shift_by_matches_reference_on_multi_word_board() {
+        let mut bb = BitBoardDyn::new(6, 70);
+        for idx in (0..bb.board().len()).filter(|i| i % 7 == 0 || i % 11 == 3) {
+            bb.board_mut().set(idx, true);
+        }
+        for d_row in -7..=7 {
+            for d_col in [-71, -69, -64, -3, -1, 0, 1, 2, 63, 65, 69, 70] {
+                assert_eq!(
+                    bb.shift_by(d_row, d_col),
+                    shift_by_reference(&bb, d_row, d_col),
+                    "d_row = {d_row}, d_col = {d_col}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn boards_default_to_hard_boundary() {
+        assert_eq!(BitBoardDyn::new(2, 2).boundary(), Boundary::Hard);
+        assert_eq!(BitBoardStatic::<1>::new(2, 2).boundary(), Boundary::Hard);
+        let bb = BitBoardDyn::new(2, 2).with_boundary(Boundary::Wrap);
+        assert_eq!(bb.boundary(), Boundary::Wrap);
+        assert_eq!(bb.or(&bb).unwrap().boundary(), Boundary::Wrap);
+    }
+
+    #[rstest]
+    #[case(0, 0, vec![(2, 0), (1, 0), (0, 2), (0, 1)])]
+    #[case(1, 1, vec![(0, 1), (2, 1), (1, 0), (1, 2)])]
+    #[case(2, 2, vec![(1, 2), (0, 2), (2, 1), (2, 0)])]
+    fn set_cardinal_neighbors_wrap_3x3(
+        #[case] row: usize,
+        #[case] col: usize,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        let mut bb = BitBoardStatic::<1>::new(3, 3).with_boundary(Boundary::Wrap);
+        bb.set_cardinal_neighbors(row, col, true);
+
+        let mut expect = BitBoardStatic::<1>::new(3, 3).with_boundary(Boundary::Wrap);
+        for (r, c) in expected {
+            expect.set(r, c, true);
+        }
+        assert_eq!(bb, expect);
+    }
+
+    #[test]
+    fn set_all_neighbors_wrap_corner() {
+        let mut bb = BitBoardDyn::new(4, 5).with_boundary(Boundary::Wrap);
+        bb.set_all_neighbors(0, 0, true);
+
+        let mut expect = BitBoardDyn::new(4, 5).with_boundary(Boundary::Wrap);
+        for (r, c) in [
+            (3, 4),
+            (3, 0),
+            (3, 1),
+            (0, 4),
+            (0, 1),
+            (1, 4),
+            (1, 0),
+            (1, 1),
+        ] {
+            expect.set(r, c, true);
+        }
+        assert_eq!(bb, expect);
+    }
+
+    #[test]
+    fn neighbor_of_respects_boundary() {
+        let hard = BitBoardDyn::new(3, 4);
+        assert_eq!(hard.neighbor_of(0, 0, -1, 0), None);
+        assert_eq!(hard.neighbor_of(2, 3, 0, 1), None);
+        assert_eq!(hard.neighbor_of(1, 1, 1, 1), Some((2, 2)));
+
+        let wrap = hard.with_boundary(Boundary::Wrap);
+        assert_eq!(wrap.neighbor_of(0, 0, -1, 0), Some((2, 0)));
+        assert_eq!(wrap.neighbor_of(2, 3, 0, 1), Some((2, 0)));
+        assert_eq!(wrap.neighbor_of(0, 0, -1, -1), Some((2, 3)));
+    }
+
+    #[test]
+    fn shift_by_wrap_matches_reference() {
+        let mut bb = BitBoardDyn::new(5, 67).with_boundary(Boundary::Wrap);
+        for idx in (0..bb.board().len()).filter(|i| i % 5 == 0 || i % 13 == 4) {
+            bb.board_mut().set(idx, true);
+        }
+        for d_row in [-11, -5, -1, 0, 1, 2, 4, 9] {
+            for d_col in [-134, -66, -1, 0, 1, 3, 66, 67, 70] {
+                let mut expected = BitBoardDyn::new(5, 67).with_boundary(Boundary::Wrap);
+                for (r, c) in (0..5).flat_map(|r| (0..67).map(move |c| (r, c))) {
+                    if bb.get(r, c) {
+                        let (nr, nc) = bb.neighbor_of(r, c, d_row, d_col).unwrap();
+                        expected.set(nr, nc, true);
+                    }
+                }
+                assert_eq!(
+                    bb.shift_by(d_row, d_col),
+                    expected,
+                    "d_row = {d_row}, d_col = {d_col}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shift_east_wraps_to_first_column() {
+        let mut bb = BitBoardStatic::<1>::new(3, 3).with_boundary(Boundary::Wrap);
+        bb.set_col(2, true);
+        let mut expected = BitBoardStatic::<1>::new(3, 3).with_boundary(Boundary::Wrap);
+        expected.set_col(0, true);
+        assert_eq!(bb.shift_east(), expected);
+        assert_eq!(expected.shift_west(), bb);
+    }
+
+    /// Count the set neighbors of every cell one at a time, to compare against.
+    fn neighbor_count_reference(bb: &BitBoardDyn, connectivity: Connectivity) -> Vec<usize> {
+        let mut counts = Vec::new();
+        for row in 0..bb.n_rows() {
+            for col in 0..bb.n_cols() {
+                let count = connectivity
+                    .offsets()
+                    .iter()
+                    .filter_map(|&(dr, dc)| bb.neighbor_of(row, col, dr, dc))
+                    .filter(|&(r, c)| bb.get(r, c))
+                    .count();
+                counts.push(count);
+            }
+        }
+        counts
+    }
+
+    fn counts_from_planes(planes: &[BitBoardDyn; 4]) -> Vec<usize> {
+        (0..planes[0].board().len())
+            .map(|idx| {
+                (0..4)
+                    .map(|bit| usize::from(planes[bit].board()[idx]) << bit)
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn patterned_board(n_rows: usize, n_cols: usize, boundary: Boundary) -> BitBoardDyn {
+        let mut bb = BitBoardDyn::new(n_rows, n_cols).with_boundary(boundary);
+        for idx in (0..bb.board().len()).filter(|i| i % 3 == 0 || i % 7 == 2) {
+            bb.board_mut().set(idx, true);
+        }
+        bb
+    }
+
+    #[rstest]
+    fn neighbor_counts_match_reference(
+        #[values(Boundary::Hard, Boundary::Wrap)] boundary: Boundary,
+        #[values(Connectivity::Four, Connectivity::Eight)] connectivity: Connectivity,
+    ) {
+        let bb = patterned_board(9, 71, boundary);
+        assert_eq!(
+            counts_from_planes(&bb.neighbor_counts(connectivity)),
+            neighbor_count_reference(&bb, connectivity)
+        );
+    }
+
+    #[test]
+    fn neighbor_counts_full_board() {
         let mut bb = BitBoardStatic::<1>::new(3, 3);
-        bb.set(0, 0, true);
+        bb.fill(true);
+        let planes = bb.neighbor_counts(Connectivity::Eight);
+        // The centre has all 8 neighbors, corners have 3, and edges have 5.
+        assert!(planes[3].get(1, 1));
+        assert!(!planes[0].get(1, 1));
+        assert!(planes[0].get(0, 0) && planes[1].get(0, 0) && !planes[2].get(0, 0));
+        assert!(planes[0].get(0, 1) && !planes[1].get(0, 1) && planes[2].get(0, 1));
+    }
+
+    #[rstest]
+    fn neighbors_exactly_and_at_least_match_reference(
+        #[values(Boundary::Hard, Boundary::Wrap)] boundary: Boundary,
+        #[values(Connectivity::Four, Connectivity::Eight)] connectivity: Connectivity,
+    ) {
+        let bb = patterned_board(6, 13, boundary);
+        let counts = neighbor_count_reference(&bb, connectivity);
+        for k in 0..=9 {
+            let exactly = bb.neighbors_exactly(k, connectivity);
+            let at_least = bb.neighbors_at_least(k, connectivity);
+            for (idx, &count) in counts.iter().enumerate() {
+                assert_eq!(exactly.board()[idx], count == k, "k = {k}, idx = {idx}");
+                assert_eq!(at_least.board()[idx], count >= k, "k = {k}, idx = {idx}");
+            }
+        }
+    }
+
+    #[test]
+    fn iter_ones_and_zeros() {
+        let mut bb = BitBoardStatic::<1>::new(2, 3);
         bb.set(0, 2, true);
-        bb.set(1, 1, true);
-        bb.set_row(2, true);
+        bb.set(1, 0, true);
 
-        assert_eq!(bb.get_row(row).collect::<Vec<bool>>(), expected);
+        assert_eq!(bb.iter_ones().collect::<Vec<_>>(), vec![(0, 2), (1, 0)]);
+        assert_eq!(
+            bb.iter_zeros().collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (1, 1), (1, 2)]
+        );
     }
 
     #[test]
-    #[should_panic(expected = "row cannot be greater than n_rows")]
-    fn get_row_oob_panics() {
-        let bb = BitBoardStatic::<1>::new(3, 3);
-        let _ = bb.get_row(3).collect::<Vec<bool>>();
+    fn iter_ones_multi_word() {
+        let mut bb = BitBoardDyn::new(3, 50);
+        let cells = vec![(0, 0), (1, 13), (1, 14), (2, 49)];
+        for &(r, c) in &cells {
+            bb.set(r, c, true);
+        }
+        assert_eq!(bb.iter_ones().collect::<Vec<_>>(), cells);
+        assert_eq!(bb.iter_zeros().count(), 150 - cells.len());
     }
 
-    #[rstest]
-    #[case(0, vec![true, false, true])]
-    #[case(1, vec![false, true, true])]
-    #[case(2, vec![true, false, true])]
-    fn get_col_3x3(#[case] col: usize, #[case] expected: Vec<bool>) {
+    #[test]
+    fn counts_and_predicates() {
         let mut bb = BitBoardStatic::<1>::new(3, 3);
-        bb.set(0, 0, true);
-        bb.set(0, 2, true);
-        bb.set(1, 1, true);
+        assert_eq!(bb.count_ones(), 0);
+        assert_eq!(bb.count_zeros(), 9);
+        assert_eq!(bb.first_one(), None);
+        assert_eq!(bb.last_one(), None);
+        assert!(bb.none() && !bb.any() && !bb.all());
+
+        bb.set(0, 1, true);
         bb.set(2, 0, true);
-        bb.set(2, 1, true);
-        bb.set(2, 2, true);
+        assert_eq!(bb.count_ones(), 2);
+        assert_eq!(bb.count_zeros(), 7);
+        assert_eq!(bb.first_one(), Some((0, 1)));
+        assert_eq!(bb.last_one(), Some((2, 0)));
+        assert!(!bb.none() && bb.any() && !bb.all());
 
-        assert_eq!(bb.get_col(col).collect::<Vec<bool>>(), expected);
+        bb.fill(true);
+        assert!(!bb.none() && bb.any() && bb.all());
     }
 
     #[test]
-    #[should_panic(expected = "col cannot be greater than n_col")]
-    fn get_col_oob_panics() {
-        let bb = BitBoardStatic::<1>::new(3, 3);
-        let _ = bb.get_col(3).collect::<Vec<bool>>();
+    fn counts_ignore_static_padding() {
+        let mut bb = BitBoardStatic::<2>::new(5, 5);
+        bb.fill(true);
+        assert!(bb.all());
+        assert_eq!(bb.count_ones(), 25);
+        assert_eq!(bb.count_zeros(), 0);
+        assert_eq!(bb.iter_zeros().count(), 0);
+        assert_eq!(bb.not().count_zeros(), 25);
+        assert!(bb.not().none());
+    }
+
+    #[rstest]
+    #[case(0, 0, Ok(0))]
+    #[case(1, 2, Ok(5))]
+    #[case(2, 0, Err(BitBoardError::OutOfBounds { row: 2, col: 0, n_rows: 2, n_cols: 3 }))]
+    #[case(0, 3, Err(BitBoardError::OutOfBounds { row: 0, col: 3, n_rows: 2, n_cols: 3 }))]
+    fn try_index_of_2x3(
+        #[case] row: usize,
+        #[case] col: usize,
+        #[case] expected: Result<usize, BitBoardError>,
+    ) {
+        let bb = BitBoardStatic::<1>::new(2, 3);
+        assert_eq!(bb.try_index_of(row, col), expected);
+    }
+
+    #[rstest]
+    #[case(0, Ok((0, 0)))]
+    #[case(5, Ok((1, 2)))]
+    #[case(6, Err(BitBoardError::IndexOutOfBounds { index: 6, len: 6 }))]
+    #[case(usize::MAX, Err(BitBoardError::IndexOutOfBounds { index: usize::MAX, len: 6 }))]
+    fn try_row_col_of_2x3(
+        #[case] index: usize,
+        #[case] expected: Result<(usize, usize), BitBoardError>,
+    ) {
+        let bb = BitBoardStatic::<1>::new(2, 3);
+        assert_eq!(bb.try_row_col_of(index), expected);
+    }
+
+    #[test]
+    fn try_set() {
+        let mut bb = BitBoardStatic::<1>::new(2, 2);
+        assert_eq!(bb.try_set(1, 0, true), Ok(()));
+        assert!(bb.get(1, 0));
+        assert_eq!(
+            bb.try_set(2, 0, true),
+            Err(BitBoardError::OutOfBounds {
+                row: 2,
+                col: 0,
+                n_rows: 2,
+                n_cols: 2,
+            })
+        );
+        assert_eq!(bb.count_ones(), 1);
+    }
+
+    #[test]
+    fn try_set_row_and_col() {
+        let mut bb = BitBoardDyn::new(3, 4);
+        assert_eq!(bb.try_set_row(2, true), Ok(()));
+        assert_eq!(bb.try_set_col(3, true), Ok(()));
+        assert_eq!(bb.count_ones(), 6);
+        assert_eq!(
+            bb.try_set_row(3, true),
+            Err(BitBoardError::RowOutOfBounds { row: 3, n_rows: 3 })
+        );
+        assert_eq!(
+            bb.try_set_col(4, true),
+            Err(BitBoardError::ColOutOfBounds { col: 4, n_cols: 4 })
+        );
+        assert_eq!(bb.count_ones(), 6);
+    }
+
+    #[test]
+    fn try_get_row_and_col() {
+        let mut bb = BitBoardDyn::new(2, 3);
+        bb.set(1, 2, true);
+        assert_eq!(
+            bb.try_get_row(1).unwrap().collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+        assert_eq!(
+            bb.try_get_col(2).unwrap().collect::<Vec<_>>(),
+            vec![false, true]
+        );
+        assert_eq!(
+            bb.try_get_row(2).err(),
+            Some(BitBoardError::RowOutOfBounds { row: 2, n_rows: 2 })
+        );
+        assert_eq!(
+            bb.try_get_col(3).err(),
+            Some(BitBoardError::ColOutOfBounds { col: 3, n_cols: 3 })
+        );
+    }
+
+    #[rstest]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    #[case(usize::MAX, usize::MAX)]
+    fn neighbor_setters_ignore_off_board_cells(
+        #[case] row: usize,
+        #[case] col: usize,
+        #[values(Boundary::Hard, Boundary::Wrap)] boundary: Boundary,
+    ) {
+        let mut bb = BitBoardStatic::<1>::new(3, 3).with_boundary(boundary);
+        bb.set_cardinal_neighbors(row, col, true);
+        bb.set_diagonals(row, col, true);
+        bb.set_all_neighbors(row, col, true);
+        assert!(bb.none());
+    }
+
+    #[test]
+    fn neighbor_of_wraps_large_offsets() {
+        let bb = BitBoardDyn::new(3, 4).with_boundary(Boundary::Wrap);
+        assert_eq!(bb.neighbor_of(2, 3, isize::MAX, isize::MIN), Some((0, 3)));
+    }
+
+    #[test]
+    fn get_mut_writes_and_toggles() {
+        let mut bb = BitBoardStatic::<1>::new(2, 3);
+        *bb.get_mut(1, 2) = true;
+        assert!(bb.get(1, 2));
+
+        let mut cell = bb.get_mut(0, 1);
+        *cell = !*cell;
+        drop(cell);
+        assert!(bb.get(0, 1));
+        assert_eq!(bb.count_ones(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn get_mut_oob_panics() {
+        let mut bb = BitBoardDyn::new(2, 2);
+        *bb.get_mut(2, 0) = true;
+    }
+
+    #[test]
+    fn neighbor_in_direction() {
+        let hard = BitBoardDyn::new(3, 3);
+        let corner = Coord::new(0, 0);
+        assert_eq!(hard.neighbor(corner, Direction::North), None);
+        assert_eq!(
+            hard.neighbor(corner, Direction::SouthEast),
+            Some(Coord::new(1, 1))
+        );
+        assert_eq!(hard.neighbor(Coord::new(3, 0), Direction::North), None);
+
+        let wrap = hard.with_boundary(Boundary::Wrap);
+        assert_eq!(
+            wrap.neighbor(corner, Direction::NorthWest),
+            Some(Coord::new(2, 2))
+        );
+    }
+
+    /// A board with no symmetry, so every transform gives a different result.
+    fn asymmetric_board() -> BitBoardDyn {
+        BitBoardDyn::from_coords(3, 5, [(0, 0), (0, 1), (1, 4), (2, 2)]).unwrap()
+    }
+
+    fn ones(bb: &impl BitBoard) -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = bb.iter_ones().collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn transpose_3x5() {
+        let result = asymmetric_board().transpose();
+        assert_eq!((result.n_rows(), result.n_cols()), (5, 3));
+        assert_eq!(ones(&result), vec![(0, 0), (1, 0), (2, 2), (4, 1)]);
+    }
+
+    #[test]
+    fn rotate_90_3x5() {
+        let result = asymmetric_board().rotate_90();
+        assert_eq!((result.n_rows(), result.n_cols()), (5, 3));
+        assert_eq!(ones(&result), vec![(0, 2), (1, 2), (2, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn rotate_180_3x5() {
+        let result = asymmetric_board().rotate_180();
+        assert_eq!((result.n_rows(), result.n_cols()), (3, 5));
+        assert_eq!(ones(&result), vec![(0, 2), (1, 0), (2, 3), (2, 4)]);
+    }
+
+    #[test]
+    fn rotate_270_3x5() {
+        let result = asymmetric_board().rotate_270();
+        assert_eq!((result.n_rows(), result.n_cols()), (5, 3));
+        assert_eq!(ones(&result), vec![(0, 1), (2, 2), (3, 0), (4, 0)]);
+    }
+
+    #[test]
+    fn flips_3x5() {
+        let board = asymmetric_board();
+        assert_eq!(
+            ones(&board.flip_horizontal()),
+            vec![(0, 3), (0, 4), (1, 0), (2, 2)]
+        );
+        assert_eq!(
+            ones(&board.flip_vertical()),
+            vec![(0, 2), (1, 4), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn transforms_compose() {
+        let board = asymmetric_board();
+        assert_eq!(board.rotate_90().rotate_90(), board.rotate_180());
+        assert_eq!(board.rotate_90().rotate_270(), board);
+        assert_eq!(board.rotate_180().rotate_180(), board);
+        assert_eq!(board.transpose().transpose(), board);
+        assert_eq!(board.flip_horizontal().flip_vertical(), board.rotate_180());
+    }
+
+    #[test]
+    fn transforms_keep_boundary() {
+        let board = asymmetric_board().with_boundary(Boundary::Wrap);
+        assert_eq!(board.transpose().boundary(), Boundary::Wrap);
+        assert_eq!(board.rotate_90().boundary(), Boundary::Wrap);
+        assert_eq!(board.flip_vertical().boundary(), Boundary::Wrap);
+    }
+
+    #[test]
+    fn canonical_is_shared_by_all_symmetries() {
+        let board = asymmetric_board();
+        let (canonical, symmetry) = board.canonical();
+        assert_eq!(symmetry.apply(&board), canonical);
+        // The smallest board has fewer rows than columns
+        assert_eq!((canonical.n_rows(), canonical.n_cols()), (3, 5));
+
+        for variant in Symmetry::ALL.map(|s| s.apply(&board)) {
+            assert_eq!(variant.canonical().0, canonical);
+        }
+    }
+
+    #[test]
+    fn canonical_static_l_tromino() {
+        let l = BitBoardStatic::<1>::from_coords(2, 2, [(0, 0), (1, 0), (1, 1)]).unwrap();
+        let (canonical, symmetry) = l.canonical();
+        // Row-major cells .X/XX are the smallest of the four orientations
+        assert_eq!(
+            canonical,
+            BitBoardStatic::<1>::from_coords(2, 2, [(0, 1), (1, 0), (1, 1)]).unwrap()
+        );
+        assert_eq!(symmetry, Symmetry::Rotate270);
+    }
+
+    #[test]
+    fn canonical_prefers_identity_on_ties() {
+        let mut board = BitBoardStatic::<1>::new(4, 4);
+        board.set(1, 1, true);
+        board.set(2, 2, true);
+        board.set(1, 2, true);
+        board.set(2, 1, true);
+        assert_eq!(board.canonical(), (board, Symmetry::Identity));
+    }
+
+    #[rstest]
+    #[case(BitBoardDyn::from_coords(3, 5, [(0, 0), (0, 1), (1, 4), (2, 2)]).unwrap(), 8)]
+    #[case(BitBoardDyn::from_coords(3, 3, [(1, 1)]).unwrap(), 1)]
+    #[case(BitBoardDyn::from_coords(3, 3, [(0, 0), (2, 2)]).unwrap(), 2)]
+    #[case(BitBoardDyn::from_coords(2, 2, [(0, 0), (1, 0), (1, 1)]).unwrap(), 4)]
+    #[case(BitBoardDyn::new(2, 3), 2)]
+    #[case(BitBoardDyn::from_coords(2, 3, [(0, 0)]).unwrap(), 8)]
+    fn distinct_symmetries(#[case] board: BitBoardDyn, #[case] expected: usize) {
+        let variants: Vec<_> = board.symmetries().collect();
+        assert_eq!(variants.len(), expected);
+        assert_eq!(variants[0], (Symmetry::Identity, board.clone()));
+        for (symmetry, variant) in &variants {
+            assert_eq!(symmetry.apply(&board), *variant);
+        }
+    }
+
+    /// A board with every third cell set, so that rows straddle word boundaries
+    /// unevenly.
+    fn striped_board(n_rows: usize, n_cols: usize) -> BitBoardDyn {
+        let mut bb = BitBoardDyn::new(n_rows, n_cols);
+        for idx in (0..n_rows * n_cols).step_by(3) {
+            bb.board_mut().set(idx, true);
+        }
+        bb
+    }
+
+    #[test]
+    fn sub_board_copies_region() {
+        let board = asymmetric_board().with_boundary(Boundary::Wrap);
+        let sub = board.sub_board(0, 1, 2, 4);
+        assert_eq!((sub.n_rows(), sub.n_cols()), (2, 4));
+        assert_eq!(ones(&sub), vec![(0, 0), (1, 3)]);
+        assert_eq!(sub.boundary(), Boundary::Wrap);
+        assert_eq!(board.sub_board(0, 0, 3, 5), board);
+    }
+
+    #[test]
+    fn sub_board_multi_word() {
+        let board = striped_board(5, 100);
+        let sub = board.sub_board(1, 30, 3, 67);
+        for row in 0..3 {
+            for col in 0..67 {
+                assert_eq!(sub.get(row, col), board.get(row + 1, col + 30));
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(0, 0, 0, 2, Err(BitBoardError::ZeroSized { n_rows: 0, n_cols: 2 }))]
+    #[case(2, 0, 2, 2, Err(BitBoardError::RegionOutOfBounds { row: 2, col: 0, height: 2, width: 2, n_rows: 3, n_cols: 5 }))]
+    #[case(0, 4, 1, 2, Err(BitBoardError::RegionOutOfBounds { row: 0, col: 4, height: 1, width: 2, n_rows: 3, n_cols: 5 }))]
+    #[case(1, 4, 2, 1, Ok(vec![(0, 0)]))]
+    fn try_sub_board_3x5(
+        #[case] row: usize,
+        #[case] col: usize,
+        #[case] height: usize,
+        #[case] width: usize,
+        #[case] expected: Result<Vec<(usize, usize)>, BitBoardError>,
+    ) {
+        let sub = asymmetric_board().try_sub_board(row, col, height, width);
+        assert_eq!(sub.map(|sub| ones(&sub)), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit on a 3x5 board")]
+    fn sub_board_out_of_bounds_panics() {
+        let _ = asymmetric_board().sub_board(1, 1, 3, 1);
+    }
+
+    #[rstest]
+    #[case(BlitMode::Copy, vec![(0, 0), (1, 1), (2, 2)])]
+    #[case(BlitMode::Or, vec![(0, 0), (1, 1), (1, 2), (2, 2)])]
+    #[case(BlitMode::And, vec![(0, 0), (2, 2)])]
+    #[case(BlitMode::Xor, vec![(0, 0), (1, 1), (1, 2)])]
+    fn blit_modes(#[case] mode: BlitMode, #[case] expected: Vec<(usize, usize)>) {
+        let mut dst = BitBoardDyn::from_coords(3, 4, [(0, 0), (1, 2), (2, 2)]).unwrap();
+        let src = BitBoardStatic::<1>::from_coords(2, 2, [(0, 0), (1, 1)]).unwrap();
+        dst.blit(&src, 1, 1, mode);
+        // Only the 2x2 region at [1, 1] changes, so (0, 0) is always kept
+        assert_eq!(ones(&dst), expected);
+    }
+
+    #[rstest]
+    #[case(-1, -2)]
+    #[case(-3, 4)]
+    #[case(2, 3)]
+    #[case(0, 0)]
+    #[case(3, -5)]
+    #[case(-4, 0)]
+    #[case(0, 7)]
+    fn blit_clips_at_edges(#[case] row: isize, #[case] col: isize) {
+        let src = striped_board(4, 6);
+        let mut dst = BitBoardDyn::new(4, 7);
+        dst.fill(true);
+        dst.blit(&src, row, col, BlitMode::Copy);
+
+        for r in 0..4 {
+            for c in 0..7 {
+                let src_r = r as isize - row;
+                let src_c = c as isize - col;
+                let expected = if (0..4).contains(&src_r) && (0..6).contains(&src_c) {
+                    src.get(src_r as usize, src_c as usize)
+                } else {
+                    true
+                };
+                assert_eq!(dst.get(r, c), expected, "({r}, {c})");
+            }
+        }
+    }
+
+    #[test]
+    fn blit_multi_word_matches_per_cell() {
+        let src = striped_board(6, 90);
+        let mut dst = BitBoardDyn::new(8, 130);
+        dst.set_row(3, true);
+        let mut expected = dst.clone();
+        for (r, c) in src.iter_ones() {
+            if r + 2 < 8 && c + 50 < 130 {
+                let cell = expected.get(r + 2, c + 50);
+                expected.set(r + 2, c + 50, !cell);
+            }
+        }
+        dst.blit(&src, 2, 50, BlitMode::Xor);
+        assert_eq!(dst, expected);
+
+        // Blitting the region back out recovers the source
+        let mut restored = BitBoardDyn::new(8, 130);
+        restored.set_row(3, true);
+        restored.blit(&dst, 0, 0, BlitMode::Xor);
+        assert_eq!(restored.sub_board(2, 50, 6, 80), src.sub_board(0, 0, 6, 80));
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![(2, 3)], Some((2, 3, 2, 3)))]
+    #[case(vec![(1, 60), (3, 5), (2, 99)], Some((1, 5, 3, 99)))]
+    #[case(vec![(0, 0), (4, 99)], Some((0, 0, 4, 99)))]
+    fn bounding_box_5x100(
+        #[case] cells: Vec<(usize, usize)>,
+        #[case] expected: Option<(usize, usize, usize, usize)>,
+    ) {
+        let bb = BitBoardDyn::from_coords(5, 100, cells).unwrap();
+        let expected = expected.map(|(min_row, min_col, max_row, max_col)| BoundingBox {
+            min_row,
+            min_col,
+            max_row,
+            max_col,
+        });
+        assert_eq!(bb.bounding_box(), expected);
+    }
+
+    #[test]
+    fn crop_to_set_cells() {
+        let bb = BitBoardStatic::<1>::from_coords(6, 7, [(1, 4), (3, 2), (3, 3)]).unwrap();
+        let cropped = bb.crop().unwrap();
+        assert_eq!((cropped.n_rows(), cropped.n_cols()), (3, 3));
+        assert_eq!(ones(&cropped), vec![(0, 2), (2, 0), (2, 1)]);
+        assert_eq!(BitBoardStatic::<1>::new(6, 7).crop(), None);
+    }
+
+    #[test]
+    fn pad_moves_cells() {
+        let mut bb = asymmetric_board().with_boundary(Boundary::Wrap);
+        bb.pad(1, 2, 3, 70);
+        assert_eq!((bb.n_rows(), bb.n_cols()), (6, 78));
+        assert_eq!(ones(&bb), vec![(1, 3), (1, 4), (2, 7), (3, 5)]);
+        assert_eq!(bb.boundary(), Boundary::Wrap);
+        assert_eq!(
+            bb.crop().unwrap(),
+            asymmetric_board().with_boundary(Boundary::Wrap)
+        );
+    }
+
+    #[test]
+    fn pad_static_within_capacity() {
+        let mut bb = BitBoardStatic::<1>::from_coords(2, 2, [(0, 0), (1, 1)]).unwrap();
+        bb.pad(0, 1, 1, 0);
+        assert_eq!((bb.n_rows(), bb.n_cols()), (3, 3));
+        assert_eq!(ones(&bb), vec![(0, 1), (1, 2)]);
+    }
+
+    #[rstest]
+    #[case(2, 3, vec![(0, 0), (0, 1)])]
+    #[case(3, 2, vec![(0, 0), (0, 1)])]
+    #[case(4, 80, vec![(0, 0), (0, 1), (1, 4), (2, 2)])]
+    #[case(1, 1, vec![(0, 0)])]
+    fn resize_keeps_row_col(
+        #[case] n_rows: usize,
+        #[case] n_cols: usize,
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        let mut bb = asymmetric_board();
+        bb.resize(n_rows, n_cols);
+        assert_eq!((bb.n_rows(), bb.n_cols()), (n_rows, n_cols));
+        assert_eq!(ones(&bb), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the allocated storage")]
+    fn resize_static_beyond_capacity_panics() {
+        let mut bb = BitBoardStatic::<1>::new(8, 8);
+        bb.resize(8, 9);
+    }
+
+    /// A ring and a U shape that touch only at a corner.
+    fn ring_and_cup() -> BitBoardDyn {
+        picture(
+            "
+            ###.....
+            #.#.....
+            ###.....
+            ...####.
+            ...#..#.
+        ",
+        )
+    }
+
+    #[rstest]
+    #[case(0, 0, Connectivity::Four, 8)]
+    #[case(0, 0, Connectivity::Eight, 14)]
+    #[case(3, 5, Connectivity::Eight, 14)]
+    #[case(1, 1, Connectivity::Four, 1)]
+    #[case(1, 1, Connectivity::Eight, 1)]
+    #[case(4, 4, Connectivity::Four, 2)]
+    #[case(0, 4, Connectivity::Four, 17)]
+    #[case(0, 4, Connectivity::Eight, 23)]
+    #[case(4, 0, Connectivity::Four, 6)]
+    fn flood_fill_region_sizes(
+        #[case] row: usize,
+        #[case] col: usize,
+        #[case] connectivity: Connectivity,
+        #[case] expected: usize,
+    ) {
+        let bb = ring_and_cup();
+        let region = bb.flood_fill(row, col, connectivity);
+        assert_eq!(region.count_ones(), expected);
+        assert!(region.get(row, col));
+        // Every cell of the region has the seed's value
+        assert!(
+            region
+                .iter_ones()
+                .all(|(r, c)| bb.get(r, c) == bb.get(row, col))
+        );
+    }
+
+    #[test]
+    fn flood_fill_wraps() {
+        let mut bb = BitBoardStatic::<1>::from_coords(3, 4, [(1, 0), (1, 3)]).unwrap();
+        assert_eq!(bb.flood_fill(1, 0, Connectivity::Four).count_ones(), 1);
+
+        bb.set_boundary(Boundary::Wrap);
+        let region = bb.flood_fill(1, 0, Connectivity::Four);
+        assert_eq!(ones(&region), vec![(1, 0), (1, 3)]);
+        assert_eq!(region.boundary(), Boundary::Wrap);
+    }
+
+    #[test]
+    fn flood_fill_multi_word() {
+        // A snake through a 5x100 board, with a last row that only touches it at a
+        // corner
+        let mut bb = BitBoardDyn::new(5, 100);
+        bb.set_row(0, true);
+        bb.set(1, 99, true);
+        bb.set_row(2, true);
+        bb.set(3, 0, true);
+        bb.set_row(4, true);
+        bb.set(4, 0, false);
+        assert_eq!(bb.flood_fill(0, 0, Connectivity::Four).count_ones(), 202);
+        assert_eq!(bb.flood_fill(0, 0, Connectivity::Eight).count_ones(), 301);
+    }
+
+    #[test]
+    fn flood_fill_in_place_paints_region() {
+        let mut bb = ring_and_cup();
+        // Fill the hole inside the ring
+        bb.flood_fill_in_place(1, 1, Connectivity::Four, true);
+        assert!(bb.get(1, 1));
+        assert_eq!(bb.count_ones(), 15);
+
+        // Clearing the cup leaves the ring
+        bb.flood_fill_in_place(3, 3, Connectivity::Four, false);
+        assert_eq!(bb.count_ones(), 9);
+        assert_eq!(bb.bounding_box().unwrap().max_col, 2);
+    }
+
+    #[test]
+    fn components_of_ring_and_cup() {
+        let bb = ring_and_cup();
+        let four = bb.connected_components(Connectivity::Four);
+        assert_eq!(four.len(), 2);
+        assert_eq!(four[0].size, 8);
+        assert_eq!(
+            four[0].bounding_box,
+            BoundingBox {
+                min_row: 0,
+                min_col: 0,
+                max_row: 2,
+                max_col: 2,
+            }
+        );
+        assert_eq!(four[1].size, 6);
+        assert_eq!(
+            four[1].bounding_box,
+            BoundingBox {
+                min_row: 3,
+                min_col: 3,
+                max_row: 4,
+                max_col: 6,
+            }
+        );
+        // Each component's cells are cropped to its box
+        assert_eq!(four[0].cells, bb.sub_board(0, 0, 3, 3));
+        assert_eq!(four[1].cells, bb.sub_board(3, 3, 2, 4));
+
+        let eight = bb.connected_components(Connectivity::Eight);
+        assert_eq!(eight.len(), 1);
+        assert_eq!(Some(eight[0].cells.clone()), bb.crop());
+        assert_eq!(eight[0].size, 14);
+    }
+
+    #[test]
+    fn components_static_wrap() {
+        // Stones on opposite edges join up when the board wraps
+        let mut bb =
+            BitBoardStatic::<1>::from_coords(4, 5, [(0, 2), (3, 2), (1, 0), (1, 4), (2, 2)])
+                .unwrap();
+        let sizes = |bb: &BitBoardStatic<1>| {
+            bb.connected_components(Connectivity::Four)
+                .iter()
+                .map(|component| component.size)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sizes(&bb), vec![1, 1, 1, 2]);
+
+        bb.set_boundary(Boundary::Wrap);
+        assert_eq!(sizes(&bb), vec![3, 2]);
+        let components = bb.connected_components(Connectivity::Four);
+        assert_eq!(components[0].bounding_box.height(), 4);
+        assert_eq!(components[1].bounding_box.width(), 5);
+    }
+
+    #[test]
+    fn components_partition_multi_word_board() {
+        let bb = striped_board(7, 90);
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let components: Vec<Component> = bb.connected_components(connectivity);
+            let mut union = BitBoardDyn::new(7, 90);
+            for component in &components {
+                let mut cells = BitBoardDyn::new(7, 90);
+                let bbox = component.bounding_box;
+                cells.blit(
+                    &component.cells,
+                    bbox.min_row as isize,
+                    bbox.min_col as isize,
+                    BlitMode::Copy,
+                );
+                assert_eq!(cells.and(&union).unwrap().count_ones(), 0);
+                union |= &cells;
+            }
+            assert_eq!(union, bb);
+            assert_eq!(
+                components.iter().map(|c| c.size).sum::<usize>(),
+                bb.count_ones()
+            );
+        }
+        assert!(
+            BitBoardDyn::new(3, 3)
+                .connected_components(Connectivity::Four)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn dilate_single_cell() {
+        let bb = BitBoardStatic::<1>::from_coords(5, 5, [(2, 2)]).unwrap();
+        let plus = bb.dilate(&Connectivity::Four.into());
+        assert_eq!(ones(&plus), vec![(1, 2), (2, 1), (2, 2), (2, 3), (3, 2)]);
+        assert_eq!(bb.dilate(&Connectivity::Eight.into()).count_ones(), 9);
+
+        // A custom element with its origin on its left end only grows to the right
+        let bar = BitBoardDyn::from_coords(1, 3, [(0, 0), (0, 1), (0, 2)]).unwrap();
+        let grown = bb.dilate(&StructuringElement::new(&bar, 0, 0));
+        assert_eq!(ones(&grown), vec![(2, 2), (2, 3), (2, 4)]);
+    }
+
+    #[test]
+    fn dilate_matches_set_all_neighbors() {
+        let bb = striped_board(6, 80);
+        let mut expected = bb.clone();
+        for (row, col) in bb.iter_ones() {
+            expected.set_all_neighbors(row, col, true);
+        }
+        assert_eq!(bb.dilate(&Connectivity::Eight.into()), expected);
+    }
+
+    #[test]
+    fn erode_at_edges() {
+        let mut full = BitBoardDyn::new(5, 70);
+        full.fill(true);
+        let eight = StructuringElement::from(Connectivity::Eight);
+        assert_eq!(full.erode(&eight).count_ones(), 3 * 68);
+        assert_eq!(
+            full.clone()
+                .with_boundary(Boundary::Wrap)
+                .erode(&eight)
+                .count_ones(),
+            350
+        );
+
+        let square = BitBoardDyn::from_coords(
+            5,
+            5,
+            [
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+            ],
+        )
+        .unwrap();
+        assert_eq!(ones(&square.erode(&eight)), vec![(2, 2)]);
+        assert_eq!(square.erode(&eight).dilate(&eight), square);
+    }
+
+    #[test]
+    fn open_and_close() {
+        let eight = StructuringElement::from(Connectivity::Eight);
+        let mut block = BitBoardDyn::new(7, 7);
+        for row in 1..6 {
+            for col in 1..6 {
+                block.set(row, col, true);
+            }
+        }
+
+        // Opening removes a speck, closing fills a pinhole
+        let mut specked = block.clone();
+        specked.set(0, 6, true);
+        assert_eq!(specked.open(&eight), block);
+
+        let mut holed = block.clone();
+        holed.set(3, 3, false);
+        assert_eq!(holed.close(&eight), block);
+    }
+
+    #[test]
+    fn gradient_outlines_square() {
+        let bb = BitBoardDyn::from_coords(
+            5,
+            5,
+            [
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+            ],
+        )
+        .unwrap();
+        let edges = bb.morphological_gradient(&Connectivity::Four.into());
+        // Everything the plus reaches, except the center that survives erosion
+        assert_eq!(edges.count_ones(), 9 + 12 - 1);
+        assert!(!edges.get(2, 2));
+        assert!(edges.get(0, 2));
+        assert!(!edges.get(0, 0));
+    }
+
+    #[test]
+    fn hit_or_miss_finds_isolated_cells() {
+        let mut ring = BitBoardDyn::new(3, 3);
+        ring.fill(true);
+        ring.set(1, 1, false);
+        let center =
+            StructuringElement::new(&BitBoardDyn::from_coords(1, 1, [(0, 0)]).unwrap(), 0, 0);
+        let around = StructuringElement::centered(&ring);
+        let bb = BitBoardDyn::from_coords(5, 6, [(0, 0), (1, 1), (3, 4), (4, 0)]).unwrap();
+        let isolated = bb.hit_or_miss(&center, &around);
+        // The cells on the edge never match, and (1, 1) touches (0, 0)
+        assert_eq!(ones(&isolated), vec![(3, 4)]);
+        assert_eq!(
+            ones(
+                &bb.with_boundary(Boundary::Wrap)
+                    .hit_or_miss(&center, &around)
+            ),
+            vec![(3, 4)]
+        );
     }
 }
@@ -1,12 +1,17 @@
 use std::fmt;
+use std::str::FromStr;
 
 use bitvec::prelude::*;
 
-use crate::{DimensionMismatch, bitboard::BitBoard};
+use crate::{
+    BitBoardError,
+    bitboard::{BitBoard, Boundary, check_dimensions},
+    parse::{ParseBoardError, ParseOptions, parse_cells},
+};
 
-/// `BitBoard` is a 2D array of booleans, stored in the bits of integers. It does
-/// assumes that the boundaries are hard, and going past a boundary does *not* take
-/// you back to the other side.
+/// `BitBoard` is a 2D array of booleans, stored in the bits of integers. By default
+/// the boundaries are hard, and going past a boundary does *not* take you back to the
+/// other side. Use [`Boundary::Wrap`] to treat the board as a torus instead.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BitBoardDyn {
     /// The slice of bits that represent the board.
@@ -17,6 +22,9 @@ pub struct BitBoardDyn {
 
     /// How many columns does the board have
     n_cols: usize,
+
+    /// What happens when a neighbor or shift goes past an edge of the board
+    boundary: Boundary,
 }
 
 impl fmt::Display for BitBoardDyn {
@@ -56,24 +64,34 @@ impl BitBoard for BitBoardDyn {
         &mut self.board
     }
 
+    fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+    }
+
     fn board(&self) -> &BitSlice {
         &self.board
     }
 
-    fn or(&self, other: &Self) -> Result<Self, DimensionMismatch> {
-        if (self.n_rows != other.n_rows()) || (self.n_cols != other.n_cols()) {
-            return Err(DimensionMismatch);
-        }
+    fn empty_with_dims(&self, n_rows: usize, n_cols: usize) -> Self {
+        BitBoardDyn::new(n_rows, n_cols).with_boundary(self.boundary)
+    }
+
+    fn or(&self, other: &Self) -> Result<Self, BitBoardError> {
+        check_dimensions(self, other)?;
         let mut new_board = BitBoardDyn::new(self.n_rows, self.n_cols);
+        new_board.boundary = self.boundary;
         new_board.board = self.board.clone() | other.board().to_bitvec();
         Ok(new_board)
     }
 
-    fn and(&self, other: &Self) -> Result<Self, DimensionMismatch> {
-        if (self.n_rows != other.n_rows()) || (self.n_cols != other.n_cols()) {
-            return Err(DimensionMismatch);
-        }
+    fn and(&self, other: &Self) -> Result<Self, BitBoardError> {
+        check_dimensions(self, other)?;
         let mut new_board = BitBoardDyn::new(self.n_rows, self.n_cols);
+        new_board.boundary = self.boundary;
         new_board.board = self.board.clone() & other.board().to_bitvec();
         Ok(new_board)
     }
@@ -91,6 +109,7 @@ impl BitBoardDyn {
             board: bitvec![0; n_rows * n_cols],
             n_rows,
             n_cols,
+            boundary: Boundary::Hard,
         }
     }
 
@@ -98,23 +117,86 @@ impl BitBoardDyn {
     ///
     /// # Errors
     ///
-    /// Returns `DimensionMismatch` if `board.len() != n_rows * n_cols`.
-    pub fn from_raw(
+    /// Returns `BitBoardError::ZeroSized` if `n_rows` or `n_cols` is 0, and
+    /// `BitBoardError::LengthMismatch` if `board.len() != n_rows * n_cols`.
+    pub fn from_raw(n_rows: usize, n_cols: usize, board: BitVec) -> Result<Self, BitBoardError> {
+        if n_rows == 0 || n_cols == 0 {
+            return Err(BitBoardError::ZeroSized { n_rows, n_cols });
+        }
+        if board.len() != n_rows * n_cols {
+            return Err(BitBoardError::LengthMismatch {
+                expected: n_rows * n_cols,
+                actual: board.len(),
+            });
+        }
+        Ok(BitBoardDyn {
+            board,
+            n_rows,
+            n_cols,
+            boundary: Boundary::Hard,
+        })
+    }
+
+    /// Create a board with the cells at each (row, col) in `coords` set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::OutOfBounds` for the first coordinate that is not on the board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_rows` or `n_cols` is 0.
+    pub fn from_coords(
         n_rows: usize,
         n_cols: usize,
-        board: BitVec,
-    ) -> Result<Self, DimensionMismatch> {
-        if n_rows == 0 || n_cols == 0 || board.len() != n_rows * n_cols {
-            return Err(DimensionMismatch);
+        coords: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, BitBoardError> {
+        let mut bb = BitBoardDyn::new(n_rows, n_cols);
+        for (row, col) in coords {
+            bb.try_set(row, col, true)?;
         }
+        Ok(bb)
+    }
+
+    /// Parse a board from text laid out as described by `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseBoardError` describing the first problem with the text.
+    pub fn parse_with(text: &str, options: &ParseOptions) -> Result<Self, ParseBoardError> {
+        let (n_rows, n_cols, board) = parse_cells(text, options)?;
         Ok(BitBoardDyn {
             board,
             n_rows,
             n_cols,
+            boundary: Boundary::Hard,
         })
     }
 }
 
+impl Extend<(usize, usize)> for BitBoardDyn {
+    /// Set the cell at each (row, col).
+    ///
+    /// # Panics
+    ///
+    /// Panics if any coordinate is out of bounds.
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, iter: I) {
+        for (row, col) in iter {
+            self.set(row, col, true);
+        }
+    }
+}
+
+impl FromStr for BitBoardDyn {
+    type Err = ParseBoardError;
+
+    /// Parses the format written by `Display`, so that `to_string().parse()`
+    /// round-trips.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &ParseOptions::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,19 +211,19 @@ mod tests {
     #[test]
     #[should_panic(expected = "Board must have at least 1 row and 1 column")]
     fn zero_rows_panics() {
-        BitBoardDyn::new(0, 3);
+        let _ = BitBoardDyn::new(0, 3);
     }
 
     #[test]
     #[should_panic(expected = "Board must have at least 1 row and 1 column")]
     fn zero_cols_panics() {
-        BitBoardDyn::new(3, 0);
+        let _ = BitBoardDyn::new(3, 0);
     }
 
     #[test]
     #[should_panic(expected = "Board must have at least 1 row and 1 column")]
     fn zero_both_panics() {
-        BitBoardDyn::new(0, 0);
+        let _ = BitBoardDyn::new(0, 0);
     }
 
     #[test]
@@ -257,10 +339,10 @@ mod tests {
     }
 
     #[rstest]
-    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 1, 0], n_rows: 2, n_cols: 2 })]
-    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 0, 1], n_rows: 2, n_cols: 2 })]
-    #[case(1, 0, BitBoardDyn { board: bitvec![1, 0, 0, 1], n_rows: 2, n_cols: 2 })]
-    #[case(1, 1, BitBoardDyn { board: bitvec![0, 1, 1, 0], n_rows: 2, n_cols: 2 })]
+    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 1, 0], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
+    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 0, 1], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
+    #[case(1, 0, BitBoardDyn { board: bitvec![1, 0, 0, 1], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
+    #[case(1, 1, BitBoardDyn { board: bitvec![0, 1, 1, 0], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
     fn set_caridnal_neighbors_2x2(
         #[case] row: usize,
         #[case] col: usize,
@@ -272,15 +354,15 @@ mod tests {
     }
 
     #[rstest]
-    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 0, 1, 0, 0, 0, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 1, 0, 1, 0, 0, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(0, 2, BitBoardDyn { board: bitvec![0, 1, 0, 0, 0, 1, 0, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(1, 0, BitBoardDyn { board: bitvec![1, 0, 0, 0, 1, 0, 1, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(1, 1, BitBoardDyn { board: bitvec![0, 1, 0, 1, 0, 1, 0, 1, 0], n_rows: 3, n_cols: 3 })]
-    #[case(1, 2, BitBoardDyn { board: bitvec![0, 0, 1, 0, 1, 0, 0, 0, 1], n_rows: 3, n_cols: 3 })]
-    #[case(2, 0, BitBoardDyn { board: bitvec![0, 0, 0, 1, 0, 0, 0, 1, 0], n_rows: 3, n_cols: 3 })]
-    #[case(2, 1, BitBoardDyn { board: bitvec![0, 0, 0, 0, 1, 0, 1, 0, 1], n_rows: 3, n_cols: 3 })]
-    #[case(2, 2, BitBoardDyn { board: bitvec![0, 0, 0, 0, 0, 1, 0, 1, 0], n_rows: 3, n_cols: 3 })]
+    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 0, 1, 0, 0, 0, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 1, 0, 1, 0, 0, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(0, 2, BitBoardDyn { board: bitvec![0, 1, 0, 0, 0, 1, 0, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(1, 0, BitBoardDyn { board: bitvec![1, 0, 0, 0, 1, 0, 1, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(1, 1, BitBoardDyn { board: bitvec![0, 1, 0, 1, 0, 1, 0, 1, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(1, 2, BitBoardDyn { board: bitvec![0, 0, 1, 0, 1, 0, 0, 0, 1], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(2, 0, BitBoardDyn { board: bitvec![0, 0, 0, 1, 0, 0, 0, 1, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(2, 1, BitBoardDyn { board: bitvec![0, 0, 0, 0, 1, 0, 1, 0, 1], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(2, 2, BitBoardDyn { board: bitvec![0, 0, 0, 0, 0, 1, 0, 1, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
     fn set_caridnal_neighbors_3x3(
         #[case] row: usize,
         #[case] col: usize,
@@ -292,10 +374,10 @@ mod tests {
     }
 
     #[rstest]
-    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 1, 1], n_rows: 2, n_cols: 2 })]
-    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 1, 1], n_rows: 2, n_cols: 2 })]
-    #[case(1, 0, BitBoardDyn { board: bitvec![1, 1, 0, 1], n_rows: 2, n_cols: 2 })]
-    #[case(1, 1, BitBoardDyn { board: bitvec![1, 1, 1, 0], n_rows: 2, n_cols: 2 })]
+    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 1, 1], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
+    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 1, 1], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
+    #[case(1, 0, BitBoardDyn { board: bitvec![1, 1, 0, 1], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
+    #[case(1, 1, BitBoardDyn { board: bitvec![1, 1, 1, 0], n_rows: 2, n_cols: 2, boundary: Boundary::Hard })]
     fn set_all_neighbors_2x2(#[case] row: usize, #[case] col: usize, #[case] expect: BitBoardDyn) {
         let mut bb = BitBoardDyn::new(2, 2);
         bb.set_all_neighbors(row, col, true);
@@ -303,15 +385,15 @@ mod tests {
     }
 
     #[rstest]
-    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 0, 1, 1, 0, 0, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 1, 1, 1, 1, 0, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(0, 2, BitBoardDyn { board: bitvec![0, 1, 0, 0, 1, 1, 0, 0, 0], n_rows: 3, n_cols: 3 })]
-    #[case(1, 0, BitBoardDyn { board: bitvec![1, 1, 0, 0, 1, 0, 1, 1, 0], n_rows: 3, n_cols: 3 })]
-    #[case(1, 1, BitBoardDyn { board: bitvec![1, 1, 1, 1, 0, 1, 1, 1, 1], n_rows: 3, n_cols: 3 })]
-    #[case(1, 2, BitBoardDyn { board: bitvec![0, 1, 1, 0, 1, 0, 0, 1, 1], n_rows: 3, n_cols: 3 })]
-    #[case(2, 0, BitBoardDyn { board: bitvec![0, 0, 0, 1, 1, 0, 0, 1, 0], n_rows: 3, n_cols: 3 })]
-    #[case(2, 1, BitBoardDyn { board: bitvec![0, 0, 0, 1, 1, 1, 1, 0, 1], n_rows: 3, n_cols: 3 })]
-    #[case(2, 2, BitBoardDyn { board: bitvec![0, 0, 0, 0, 1, 1, 0, 1, 0], n_rows: 3, n_cols: 3 })]
+    #[case(0, 0, BitBoardDyn { board: bitvec![0, 1, 0, 1, 1, 0, 0, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(0, 1, BitBoardDyn { board: bitvec![1, 0, 1, 1, 1, 1, 0, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(0, 2, BitBoardDyn { board: bitvec![0, 1, 0, 0, 1, 1, 0, 0, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(1, 0, BitBoardDyn { board: bitvec![1, 1, 0, 0, 1, 0, 1, 1, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(1, 1, BitBoardDyn { board: bitvec![1, 1, 1, 1, 0, 1, 1, 1, 1], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(1, 2, BitBoardDyn { board: bitvec![0, 1, 1, 0, 1, 0, 0, 1, 1], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(2, 0, BitBoardDyn { board: bitvec![0, 0, 0, 1, 1, 0, 0, 1, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(2, 1, BitBoardDyn { board: bitvec![0, 0, 0, 1, 1, 1, 1, 0, 1], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
+    #[case(2, 2, BitBoardDyn { board: bitvec![0, 0, 0, 0, 1, 1, 0, 1, 0], n_rows: 3, n_cols: 3, boundary: Boundary::Hard })]
     fn set_all_neighbors_3x3(#[case] row: usize, #[case] col: usize, #[case] expect: BitBoardDyn) {
         let mut bb = BitBoardDyn::new(3, 3);
         bb.set_all_neighbors(row, col, true);
@@ -360,11 +442,13 @@ mod tests {
             board: board1,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
         let bb2 = BitBoardDyn {
             board: board2,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         let result = bb1.and(&bb2).unwrap();
@@ -386,11 +470,13 @@ mod tests {
             board: board1,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
         let bb2 = BitBoardDyn {
             board: board2,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         let result = bb1.or(&bb2).unwrap();
@@ -449,4 +535,132 @@ mod tests {
         assert_eq!(bb1, bb1_original);
         assert_eq!(bb2, bb2_original);
     }
+
+    #[rstest]
+    #[case(bitvec![0, 0, 0, 0], bitvec![0, 0, 0, 0], bitvec![0, 0, 0, 0])] // empty XOR empty
+    #[case(bitvec![1, 1, 1, 1], bitvec![1, 1, 1, 1], bitvec![0, 0, 0, 0])] // full XOR full
+    #[case(bitvec![0, 0, 0, 0], bitvec![1, 0, 0, 1], bitvec![1, 0, 0, 1])] // empty XOR partial
+    #[case(bitvec![1, 1, 0, 0], bitvec![1, 0, 1, 0], bitvec![0, 1, 1, 0])] // partial patterns
+    fn xor_operations(#[case] board1: BitVec, #[case] board2: BitVec, #[case] expected: BitVec) {
+        let bb1 = BitBoardDyn::from_raw(2, 2, board1).unwrap();
+        let bb2 = BitBoardDyn::from_raw(2, 2, board2).unwrap();
+
+        let result = bb1.xor(&bb2).unwrap();
+        assert_eq!(result.board().to_bitvec(), expected);
+    }
+
+    #[rstest]
+    #[case(bitvec![0, 0, 0, 0], bitvec![1, 1, 1, 1], bitvec![0, 0, 0, 0])] // empty minus full
+    #[case(bitvec![1, 1, 1, 1], bitvec![0, 0, 0, 0], bitvec![1, 1, 1, 1])] // full minus empty
+    #[case(bitvec![1, 1, 1, 1], bitvec![1, 0, 0, 1], bitvec![0, 1, 1, 0])] // full minus partial
+    #[case(bitvec![1, 1, 0, 0], bitvec![1, 0, 1, 0], bitvec![0, 1, 0, 0])] // partial patterns
+    fn and_not_operations(
+        #[case] board1: BitVec,
+        #[case] board2: BitVec,
+        #[case] expected: BitVec,
+    ) {
+        let bb1 = BitBoardDyn::from_raw(2, 2, board1).unwrap();
+        let bb2 = BitBoardDyn::from_raw(2, 2, board2).unwrap();
+
+        let result = bb1.and_not(&bb2).unwrap();
+        assert_eq!(result.board().to_bitvec(), expected);
+    }
+
+    #[test]
+    fn not_operation() {
+        let bb = BitBoardDyn::from_raw(2, 3, bitvec![1, 0, 0, 1, 1, 0]).unwrap();
+        assert_eq!(bb.not().board().to_bitvec(), bitvec![0, 1, 1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn from_coords() {
+        let bb = BitBoardDyn::from_coords(3, 4, vec![(0, 0), (1, 3), (2, 1)]).unwrap();
+        assert_eq!(
+            bb.iter_ones().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 3), (2, 1)]
+        );
+    }
+
+    #[rstest]
+    #[case((3, 0))]
+    #[case((0, 4))]
+    #[case((10, 10))]
+    fn from_coords_out_of_bounds(#[case] coord: (usize, usize)) {
+        let err = BitBoardDyn::from_coords(3, 4, [(1, 1), coord]).unwrap_err();
+        assert_eq!(
+            err,
+            BitBoardError::OutOfBounds {
+                row: coord.0,
+                col: coord.1,
+                n_rows: 3,
+                n_cols: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn extend_sets_cells() {
+        let mut bb = BitBoardDyn::new(3, 3);
+        bb.extend([(0, 1), (2, 2)]);
+        bb.extend(vec![(1, 1)]);
+        assert_eq!(
+            bb,
+            BitBoardDyn::from_coords(3, 3, [(0, 1), (1, 1), (2, 2)]).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn extend_out_of_bounds_panics() {
+        let mut bb = BitBoardDyn::new(3, 3);
+        bb.extend([(3, 0)]);
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(3, 4)]
+    #[case(12, 17)]
+    fn display_round_trips(#[case] n_rows: usize, #[case] n_cols: usize) {
+        let mut bb = BitBoardDyn::new(n_rows, n_cols);
+        for idx in (0..n_rows * n_cols).filter(|i| i % 3 == 0 || i % 5 == 1) {
+            let (row, col) = bb.row_col_of(idx);
+            bb.set(row, col, true);
+        }
+        let parsed: BitBoardDyn = bb.to_string().parse().unwrap();
+        assert_eq!(parsed, bb);
+    }
+
+    #[rstest]
+    #[case(0, 2, bitvec![], BitBoardError::ZeroSized { n_rows: 0, n_cols: 2 })]
+    #[case(2, 0, bitvec![], BitBoardError::ZeroSized { n_rows: 2, n_cols: 0 })]
+    #[case(2, 2, bitvec![0, 1, 0], BitBoardError::LengthMismatch { expected: 4, actual: 3 })]
+    fn from_raw_errors(
+        #[case] n_rows: usize,
+        #[case] n_cols: usize,
+        #[case] board: BitVec,
+        #[case] expected: BitBoardError,
+    ) {
+        assert_eq!(
+            BitBoardDyn::from_raw(n_rows, n_cols, board).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn dimension_mismatch_reports_dimensions() {
+        let err = BitBoardDyn::new(2, 3)
+            .or(&BitBoardDyn::new(4, 5))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BitBoardError::DimensionMismatch {
+                expected: (2, 3),
+                actual: (4, 5),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Dimensions do not match: expected 2x3, found 4x5."
+        );
+    }
 }
@@ -1,9 +1,17 @@
 use std::fmt;
 use std::ops::{BitAndAssign, BitOrAssign};
+use std::str::FromStr;
 
 use bitvec::prelude::*;
 
-use crate::{DimensionMismatch, bitboard::BitBoard};
+use crate::{
+    BitBoardError,
+    bitboard::{
+        BitBoard, Boundary, check_dimensions, flipped_horizontal, flipped_vertical, rotated_180,
+        transposed,
+    },
+    parse::{ParseBoardError, ParseOptions, parse_cells},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BitBoardStatic<const W: usize> {
@@ -15,6 +23,9 @@ pub struct BitBoardStatic<const W: usize> {
 
     /// How many columns does the board have
     n_cols: usize,
+
+    /// What happens when a neighbor or shift goes past an edge of the board
+    boundary: Boundary,
 }
 
 impl<const W: usize> fmt::Display for BitBoardStatic<W> {
@@ -64,28 +75,87 @@ impl<const W: usize> BitBoardStatic<W> {
             board: BitArray::default(),
             n_rows,
             n_cols,
+            boundary: Boundary::Hard,
         }
     }
 
+    /// Returns an error unless a board of `n_rows` x `n_cols` fits in `W` words.
+    fn check_size(n_rows: usize, n_cols: usize) -> Result<(), BitBoardError> {
+        if n_rows == 0 || n_cols == 0 {
+            return Err(BitBoardError::ZeroSized { n_rows, n_cols });
+        }
+        let total_bits = n_rows * n_cols;
+        let available_bits = W * (usize::BITS as usize);
+        if total_bits > available_bits {
+            return Err(BitBoardError::ExceedsCapacity {
+                required: total_bits,
+                available: available_bits,
+            });
+        }
+        Ok(())
+    }
+
     /// Create a board from pre-existing data.
     ///
     /// # Errors
     ///
-    /// Returns `DimensionMismatch` if `n_rows * n_cols` exceeds the storage capacity.
+    /// Returns `BitBoardError::ZeroSized` if `n_rows` or `n_cols` is 0, and
+    /// `BitBoardError::ExceedsCapacity` if `n_rows * n_cols` exceeds the storage capacity.
+    ///
+    /// Any bits past `n_rows * n_cols` are cleared, so that the unused padding of
+    /// the backing array is always zero.
     pub fn from_raw(
         n_rows: usize,
         n_cols: usize,
-        board: BitArray<[usize; W]>,
-    ) -> Result<Self, DimensionMismatch> {
-        let total_bits = n_rows * n_cols;
-        let available_bits = W * (usize::BITS as usize);
-        if n_rows == 0 || n_cols == 0 || total_bits > available_bits {
-            return Err(DimensionMismatch);
+        mut board: BitArray<[usize; W]>,
+    ) -> Result<Self, BitBoardError> {
+        Self::check_size(n_rows, n_cols)?;
+        board[n_rows * n_cols..].fill(false);
+        Ok(Self {
+            board,
+            n_rows,
+            n_cols,
+            boundary: Boundary::Hard,
+        })
+    }
+
+    /// Create a board with the cells at each (row, col) in `coords` set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BitBoardError::OutOfBounds` for the first coordinate that is not on the board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_rows` or `n_cols` is 0, or if the board does not fit in the allocated storage.
+    pub fn from_coords(
+        n_rows: usize,
+        n_cols: usize,
+        coords: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, BitBoardError> {
+        let mut bb = Self::new(n_rows, n_cols);
+        for (row, col) in coords {
+            bb.try_set(row, col, true)?;
         }
+        Ok(bb)
+    }
+
+    /// Parse a board from text laid out as described by `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseBoardError` describing the first problem with the text,
+    /// including if the board does not fit in the allocated storage.
+    pub fn parse_with(text: &str, options: &ParseOptions) -> Result<Self, ParseBoardError> {
+        let (n_rows, n_cols, bits) = parse_cells(text, options)?;
+        Self::check_size(n_rows, n_cols)?;
+        let mut board = BitArray::<[usize; W]>::default();
+        board[..bits.len()].copy_from_bitslice(&bits);
         Ok(Self {
             board,
             n_rows,
             n_cols,
+            boundary: Boundary::Hard,
         })
     }
 }
@@ -100,18 +170,29 @@ impl<const W: usize> BitBoard for BitBoardStatic<W> {
     }
 
     fn board_mut(&mut self) -> &mut BitSlice {
-        &mut self.board
+        let n_bits = self.n_rows * self.n_cols;
+        &mut self.board[..n_bits]
+    }
+
+    fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
     }
 
     fn board(&self) -> &BitSlice {
-        &self.board
+        &self.board[..self.n_rows * self.n_cols]
+    }
+
+    fn empty_with_dims(&self, n_rows: usize, n_cols: usize) -> Self {
+        Self::new(n_rows, n_cols).with_boundary(self.boundary)
     }
 
     /// Performs a bitwise OR operation between two bitboards.
-    fn or(&self, other: &Self) -> Result<Self, DimensionMismatch> {
-        if (self.n_rows() != other.n_rows()) || (self.n_cols() != other.n_cols()) {
-            return Err(DimensionMismatch);
-        }
+    fn or(&self, other: &Self) -> Result<Self, BitBoardError> {
+        check_dimensions(self, other)?;
 
         let mut result = *self;
         result.board_mut().bitor_assign(other.board());
@@ -119,15 +200,119 @@ impl<const W: usize> BitBoard for BitBoardStatic<W> {
     }
 
     /// Performs a bitwise AND operation between two bitboards.
-    fn and(&self, other: &Self) -> Result<Self, DimensionMismatch> {
-        if (self.n_rows() != other.n_rows()) || (self.n_cols() != other.n_cols()) {
-            return Err(DimensionMismatch);
-        }
+    fn and(&self, other: &Self) -> Result<Self, BitBoardError> {
+        check_dimensions(self, other)?;
 
         let mut result = *self;
         result.board_mut().bitand_assign(other.board());
         Ok(result)
     }
+
+    /// Uses a delta-swap on the whole word for 8x8 boards.
+    fn transpose(&self) -> Self {
+        match self.as_8x8() {
+            Some(word) => self.with_8x8(transpose_8x8(word)),
+            None => transposed(self),
+        }
+    }
+
+    /// Uses a bit reversal of the whole word for 8x8 boards.
+    fn rotate_180(&self) -> Self {
+        match self.as_8x8() {
+            Some(word) => self.with_8x8(word.reverse_bits()),
+            None => rotated_180(self),
+        }
+    }
+
+    /// Uses delta-swaps on the whole word for 8x8 boards.
+    fn flip_horizontal(&self) -> Self {
+        match self.as_8x8() {
+            Some(word) => self.with_8x8(flip_horizontal_8x8(word)),
+            None => flipped_horizontal(self),
+        }
+    }
+
+    /// Uses a byte swap of the whole word for 8x8 boards.
+    fn flip_vertical(&self) -> Self {
+        match self.as_8x8() {
+            Some(word) => self.with_8x8(word.swap_bytes()),
+            None => flipped_vertical(self),
+        }
+    }
+}
+
+impl<const W: usize> BitBoardStatic<W> {
+    /// Returns the board as a single 64-bit word if it is an 8x8 board held in one
+    /// word. Bit `8 * row + col` of the word is the cell at [row, col], so each row is
+    /// one byte.
+    fn as_8x8(&self) -> Option<u64> {
+        if W == 1 && usize::BITS == 64 && self.n_rows == 8 && self.n_cols == 8 {
+            Some(self.board.as_raw_slice()[0] as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an 8x8 board with the same boundary as this one, holding `word`.
+    fn with_8x8(&self, word: u64) -> Self {
+        let mut board = BitArray::<[usize; W]>::ZERO;
+        board.as_raw_mut_slice()[0] = word as usize;
+        Self {
+            board,
+            n_rows: 8,
+            n_cols: 8,
+            boundary: self.boundary,
+        }
+    }
+}
+
+/// Mirrors an 8x8 board across its main diagonal with three delta-swaps, exchanging
+/// 4x4, then 2x2, then 1x1 blocks.
+fn transpose_8x8(mut x: u64) -> u64 {
+    const K1: u64 = 0x5500_5500_5500_5500;
+    const K2: u64 = 0x3333_0000_3333_0000;
+    const K4: u64 = 0x0f0f_0f0f_0000_0000;
+    let t = K4 & (x ^ (x << 28));
+    x ^= t ^ (t >> 28);
+    let t = K2 & (x ^ (x << 14));
+    x ^= t ^ (t >> 14);
+    let t = K1 & (x ^ (x << 7));
+    x ^= t ^ (t >> 7);
+    x
+}
+
+/// Reverses the bits within each byte, i.e. each row, of an 8x8 board.
+fn flip_horizontal_8x8(mut x: u64) -> u64 {
+    const K1: u64 = 0x5555_5555_5555_5555;
+    const K2: u64 = 0x3333_3333_3333_3333;
+    const K4: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+    x = ((x >> 1) & K1) | ((x & K1) << 1);
+    x = ((x >> 2) & K2) | ((x & K2) << 2);
+    x = ((x >> 4) & K4) | ((x & K4) << 4);
+    x
+}
+
+impl<const W: usize> Extend<(usize, usize)> for BitBoardStatic<W> {
+    /// Set the cell at each (row, col).
+    ///
+    /// # Panics
+    ///
+    /// Panics if any coordinate is out of bounds.
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, iter: I) {
+        for (row, col) in iter {
+            self.set(row, col, true);
+        }
+    }
+}
+
+impl<const W: usize> FromStr for BitBoardStatic<W> {
+    type Err = ParseBoardError;
+
+    /// Parses the format written by `Display`, so that `to_string().parse()`
+    /// round-trips.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &ParseOptions::default())
+    }
 }
 
 #[cfg(test)]
@@ -146,19 +331,19 @@ mod tests {
     #[test]
     #[should_panic(expected = "Board must have at least 1 row and 1 column")]
     fn zero_rows_panics() {
-        BitBoardStatic::<1>::new(0, 3);
+        let _ = BitBoardStatic::<1>::new(0, 3);
     }
 
     #[test]
     #[should_panic(expected = "Board must have at least 1 row and 1 column")]
     fn zero_cols_panics() {
-        BitBoardStatic::<1>::new(3, 0);
+        let _ = BitBoardStatic::<1>::new(3, 0);
     }
 
     #[test]
     #[should_panic(expected = "Board must have at least 1 row and 1 column")]
     fn zero_both_panics() {
-        BitBoardStatic::<1>::new(0, 0);
+        let _ = BitBoardStatic::<1>::new(0, 0);
     }
 
     #[test]
@@ -301,6 +486,7 @@ mod tests {
             board: expect_board,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         assert_eq!(expect, bb);
@@ -331,6 +517,7 @@ mod tests {
             board: expect_board,
             n_rows: 3,
             n_cols: 3,
+            boundary: Boundary::Hard,
         };
 
         assert_eq!(expect, bb);
@@ -352,6 +539,7 @@ mod tests {
             board: expect_board,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         assert_eq!(expect, bb);
@@ -378,6 +566,7 @@ mod tests {
             board: expect_board,
             n_rows: 3,
             n_cols: 3,
+            boundary: Boundary::Hard,
         };
 
         assert_eq!(expect, bb);
@@ -431,6 +620,7 @@ mod tests {
             board: board1_arr,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         let mut board2_arr = BitArray::<[usize; 1]>::default();
@@ -440,6 +630,7 @@ mod tests {
             board: board2_arr,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         let result = bb1.and(&bb2).unwrap();
@@ -470,6 +661,7 @@ mod tests {
             board: board1_arr,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         let mut board2_arr = BitArray::<[usize; 1]>::default();
@@ -478,6 +670,7 @@ mod tests {
             board: board2_arr,
             n_rows: 2,
             n_cols: 2,
+            boundary: Boundary::Hard,
         };
 
         let result = bb1.or(&bb2).unwrap();
@@ -538,4 +731,201 @@ mod tests {
         assert_eq!(bb1, bb1_original);
         assert_eq!(bb2, bb2_original);
     }
+
+    #[test]
+    fn not_keeps_padding_zero() {
+        let bb = BitBoardStatic::<1>::new(3, 3);
+        let flipped = bb.not();
+        assert!(flipped.board[..9].all());
+        assert!(flipped.board[9..].not_any());
+    }
+
+    #[test]
+    fn fill_keeps_padding_zero() {
+        let mut bb = BitBoardStatic::<2>::new(7, 11);
+        bb.fill(true);
+        assert!(bb.board[..77].all());
+        assert!(bb.board[77..].not_any());
+    }
+
+    #[test]
+    fn from_raw_clears_padding() {
+        let bb = BitBoardStatic::<1>::from_raw(2, 2, BitArray::new([usize::MAX])).unwrap();
+        assert!(bb.board[..4].all());
+        assert!(bb.board[4..].not_any());
+        assert_eq!(bb.board().len(), 4);
+    }
+
+    #[test]
+    fn from_coords() {
+        let bb = BitBoardStatic::<1>::from_coords(3, 4, vec![(0, 0), (1, 3), (2, 1)]).unwrap();
+        assert_eq!(
+            bb.iter_ones().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 3), (2, 1)]
+        );
+    }
+
+    #[rstest]
+    #[case((3, 0))]
+    #[case((0, 4))]
+    #[case((10, 10))]
+    fn from_coords_out_of_bounds(#[case] coord: (usize, usize)) {
+        let err = BitBoardStatic::<1>::from_coords(3, 4, [(1, 1), coord]).unwrap_err();
+        assert_eq!(
+            err,
+            BitBoardError::OutOfBounds {
+                row: coord.0,
+                col: coord.1,
+                n_rows: 3,
+                n_cols: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn extend_sets_cells() {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.extend([(0, 1), (2, 2)]);
+        bb.extend(vec![(1, 1)]);
+        assert_eq!(
+            bb,
+            BitBoardStatic::<1>::from_coords(3, 3, [(0, 1), (1, 1), (2, 2)]).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn extend_out_of_bounds_panics() {
+        let mut bb = BitBoardStatic::<1>::new(3, 3);
+        bb.extend([(3, 0)]);
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(3, 4)]
+    #[case(12, 17)]
+    fn display_round_trips(#[case] n_rows: usize, #[case] n_cols: usize) {
+        let mut bb = BitBoardStatic::<4>::new(n_rows, n_cols);
+        for idx in (0..n_rows * n_cols).filter(|i| i % 3 == 0 || i % 5 == 1) {
+            let (row, col) = bb.row_col_of(idx);
+            bb.set(row, col, true);
+        }
+        let parsed: BitBoardStatic<4> = bb.to_string().parse().unwrap();
+        assert_eq!(parsed, bb);
+    }
+
+    #[test]
+    fn parse_exceeds_capacity() {
+        let text = BitBoardStatic::<2>::new(10, 10).to_string();
+        assert_eq!(
+            text.parse::<BitBoardStatic<1>>().unwrap_err(),
+            ParseBoardError::Board(BitBoardError::ExceedsCapacity {
+                required: 100,
+                available: 64,
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(0, 2, BitBoardError::ZeroSized { n_rows: 0, n_cols: 2 })]
+    #[case(2, 0, BitBoardError::ZeroSized { n_rows: 2, n_cols: 0 })]
+    #[case(9, 8, BitBoardError::ExceedsCapacity { required: 72, available: 64 })]
+    fn from_raw_errors(
+        #[case] n_rows: usize,
+        #[case] n_cols: usize,
+        #[case] expected: BitBoardError,
+    ) {
+        assert_eq!(
+            BitBoardStatic::<1>::from_raw(n_rows, n_cols, BitArray::ZERO).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn dimension_mismatch_reports_dimensions() {
+        let bb1 = BitBoardStatic::<1>::new(2, 3);
+        let bb2 = BitBoardStatic::<1>::new(3, 2);
+        assert_eq!(
+            bb1.and(&bb2).unwrap_err(),
+            BitBoardError::DimensionMismatch {
+                expected: (2, 3),
+                actual: (3, 2),
+            }
+        );
+    }
+
+    fn patterned_8x8<const N: usize>() -> BitBoardStatic<N> {
+        let mut bb = BitBoardStatic::<N>::new(8, 8);
+        for (row, col) in [
+            (0, 0),
+            (0, 1),
+            (0, 7),
+            (1, 3),
+            (2, 6),
+            (5, 2),
+            (6, 7),
+            (7, 0),
+        ] {
+            bb.set(row, col, true);
+        }
+        bb
+    }
+
+    fn sorted_ones(bb: &impl BitBoard) -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = bb.iter_ones().collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn fast_8x8_transforms_match_generic() {
+        // W = 2 has no fast path, so it exercises the generic versions
+        let fast = patterned_8x8::<1>();
+        let slow = patterned_8x8::<2>();
+        assert!(fast.as_8x8().is_some());
+        assert!(slow.as_8x8().is_none());
+
+        assert_eq!(
+            sorted_ones(&fast.transpose()),
+            sorted_ones(&slow.transpose())
+        );
+        assert_eq!(
+            sorted_ones(&fast.rotate_90()),
+            sorted_ones(&slow.rotate_90())
+        );
+        assert_eq!(
+            sorted_ones(&fast.rotate_180()),
+            sorted_ones(&slow.rotate_180())
+        );
+        assert_eq!(
+            sorted_ones(&fast.rotate_270()),
+            sorted_ones(&slow.rotate_270())
+        );
+        assert_eq!(
+            sorted_ones(&fast.flip_horizontal()),
+            sorted_ones(&slow.flip_horizontal())
+        );
+        assert_eq!(
+            sorted_ones(&fast.flip_vertical()),
+            sorted_ones(&slow.flip_vertical())
+        );
+    }
+
+    #[test]
+    fn fast_8x8_transpose() {
+        let bb = BitBoardStatic::<1>::from_coords(8, 8, [(0, 1), (2, 7), (6, 6)]).unwrap();
+        assert_eq!(
+            bb.transpose(),
+            BitBoardStatic::<1>::from_coords(8, 8, [(1, 0), (7, 2), (6, 6)]).unwrap()
+        );
+    }
+
+    #[test]
+    fn transforms_non_square_static() {
+        let bb = BitBoardStatic::<1>::from_coords(2, 3, [(0, 0), (1, 2)]).unwrap();
+        let rotated = bb.rotate_90();
+        assert_eq!((rotated.n_rows(), rotated.n_cols()), (3, 2));
+        assert_eq!(sorted_ones(&rotated), vec![(0, 1), (2, 0)]);
+        assert!(rotated.board[6..].not_any());
+    }
 }
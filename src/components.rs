@@ -0,0 +1,281 @@
+//! Connected-component labeling in a single pass: the set cells of each row are split
+//! into runs, and runs that touch are joined with a union-find.
+
+use crate::bitboard::{BitBoard, Boundary, Component, Connectivity};
+use crate::bitboarddyn::BitBoardDyn;
+use crate::coord::BoundingBox;
+
+/// A run of set cells in one row, covering the columns `start..end`.
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+/// Returns the runs of set cells, in row-major order, and the index of the first run
+/// of each row, with one more entry at the end for the total.
+fn find_runs<B: BitBoard>(board: &B) -> (Vec<Run>, Vec<usize>) {
+    let n_cols = board.n_cols();
+    let mut runs = Vec::new();
+    let mut row_starts = Vec::with_capacity(board.n_rows() + 1);
+    for row in 0..board.n_rows() {
+        row_starts.push(runs.len());
+        let line = &board.board()[row * n_cols..(row + 1) * n_cols];
+        let mut col = 0;
+        // Skip a word at a time to the start and then the end of each run
+        while let Some(start) = line[col..].first_one().map(|offset| col + offset) {
+            let end = line[start..]
+                .first_zero()
+                .map_or(n_cols, |offset| start + offset);
+            runs.push(Run { row, start, end });
+            col = end;
+        }
+    }
+    row_starts.push(runs.len());
+    (runs, row_starts)
+}
+
+/// A union-find over run indices, where the root of each set is its smallest index.
+struct Parents(Vec<usize>);
+
+impl Parents {
+    fn root(&mut self, mut idx: usize) -> usize {
+        while self.0[idx] != idx {
+            self.0[idx] = self.0[self.0[idx]];
+            idx = self.0[idx];
+        }
+        idx
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.root(a), self.root(b));
+        self.0[a.max(b)] = a.min(b);
+    }
+}
+
+/// Joins each run in `below` to the runs in `above` that it touches, where `above` is
+/// the row before `below`. Both are sorted, non-overlapping ranges of run indices.
+fn join_rows(
+    runs: &[Run],
+    parents: &mut Parents,
+    below: std::ops::Range<usize>,
+    above: std::ops::Range<usize>,
+    connectivity: Connectivity,
+    wrap_cols: Option<usize>,
+) {
+    // Diagonal neighbors reach one column further on each side
+    let reach = usize::from(connectivity == Connectivity::Eight);
+    let (mut i, mut j) = (below.start, above.start);
+    while i < below.end && j < above.end {
+        let (a, b) = (runs[i], runs[j]);
+        if a.start < b.end + reach && b.start < a.end + reach {
+            parents.join(i, j);
+        }
+        // Drop whichever run can no longer touch a later run of the other row
+        if a.end + reach <= b.end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    // Diagonal steps across the side edges of a wrapping board, which only the first
+    // and last runs of a row can reach
+    if reach == 1
+        && let Some(n_cols) = wrap_cols
+        && !below.is_empty()
+        && !above.is_empty()
+    {
+        let (first_a, last_a) = (below.start, below.end - 1);
+        let (first_b, last_b) = (above.start, above.end - 1);
+        if runs[first_a].start == 0 && runs[last_b].end == n_cols {
+            parents.join(first_a, last_b);
+        }
+        if runs[last_a].end == n_cols && runs[first_b].start == 0 {
+            parents.join(last_a, first_b);
+        }
+    }
+}
+
+/// Returns the component index of each run, numbered by first cell in row-major order,
+/// along with the runs and the number of components.
+fn label_runs<B: BitBoard>(board: &B, connectivity: Connectivity) -> (Vec<Run>, Vec<usize>, usize) {
+    let (runs, row_starts) = find_runs(board);
+    let (n_rows, n_cols) = (board.n_rows(), board.n_cols());
+    let wrap = board.boundary() == Boundary::Wrap;
+    let row_runs = |row: usize| row_starts[row]..row_starts[row + 1];
+    let mut parents = Parents((0..runs.len()).collect());
+
+    for row in 1..n_rows {
+        let wrap_cols = wrap.then_some(n_cols);
+        join_rows(
+            &runs,
+            &mut parents,
+            row_runs(row),
+            row_runs(row - 1),
+            connectivity,
+            wrap_cols,
+        );
+    }
+    if wrap {
+        // The last row is above the first
+        if n_rows > 1 {
+            join_rows(
+                &runs,
+                &mut parents,
+                row_runs(0),
+                row_runs(n_rows - 1),
+                connectivity,
+                Some(n_cols),
+            );
+        }
+        // A run ending on the right edge continues from the left edge
+        for row in 0..n_rows {
+            let range = row_runs(row);
+            if range.len() > 1 && runs[range.start].start == 0 && runs[range.end - 1].end == n_cols
+            {
+                parents.join(range.start, range.end - 1);
+            }
+        }
+    }
+
+    // Roots are the smallest index in their set, so they come before the rest of it
+    let mut labels = vec![usize::MAX; runs.len()];
+    let mut n_components = 0;
+    for idx in 0..runs.len() {
+        let root = parents.root(idx);
+        if root == idx {
+            labels[idx] = n_components;
+            n_components += 1;
+        } else {
+            labels[idx] = labels[root];
+        }
+    }
+    (runs, labels, n_components)
+}
+
+/// Returns the component index of each cell in row-major order, or `None` for unset
+/// cells.
+pub(crate) fn labels<B: BitBoard>(board: &B, connectivity: Connectivity) -> Vec<Option<usize>> {
+    let (runs, labels, _) = label_runs(board, connectivity);
+    let mut grid = vec![None; board.n_rows() * board.n_cols()];
+    for (run, label) in runs.iter().zip(labels) {
+        let start = board.index_of(run.row, run.start);
+        grid[start..start + run.end - run.start].fill(Some(label));
+    }
+    grid
+}
+
+/// Returns each component with its cells cropped to its bounding box.
+pub(crate) fn components<B: BitBoard>(board: &B, connectivity: Connectivity) -> Vec<Component> {
+    let (runs, labels, n_components) = label_runs(board, connectivity);
+
+    let mut sizes = vec![0; n_components];
+    let mut boxes: Vec<Option<BoundingBox>> = vec![None; n_components];
+    for (run, &label) in runs.iter().zip(&labels) {
+        sizes[label] += run.end - run.start;
+        let bbox = boxes[label].get_or_insert(BoundingBox {
+            min_row: run.row,
+            min_col: run.start,
+            max_row: run.row,
+            max_col: run.end - 1,
+        });
+        bbox.min_col = bbox.min_col.min(run.start);
+        bbox.max_row = bbox.max_row.max(run.row);
+        bbox.max_col = bbox.max_col.max(run.end - 1);
+    }
+
+    let mut components: Vec<Component> = sizes
+        .into_iter()
+        .zip(boxes)
+        .map(|(size, bbox)| {
+            let bounding_box = bbox.expect("every component has a run");
+            Component {
+                cells: BitBoardDyn::new(bounding_box.height(), bounding_box.width())
+                    .with_boundary(board.boundary()),
+                size,
+                bounding_box,
+            }
+        })
+        .collect();
+    for (run, label) in runs.iter().zip(labels) {
+        let component = &mut components[label];
+        let (bbox, width) = (component.bounding_box, component.bounding_box.width());
+        let start = (run.row - bbox.min_row) * width + run.start - bbox.min_col;
+        component.cells.board_mut()[start..start + run.end - run.start].fill(true);
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitboard::BlitMode, bitboardstatic::BitBoardStatic};
+    use rstest::rstest;
+
+    /// Finds the components one flood fill at a time, as full-size boards.
+    fn flood_filled(board: &BitBoardDyn, connectivity: Connectivity) -> Vec<BitBoardDyn> {
+        let mut components = Vec::new();
+        let mut remaining = board.clone();
+        while let Some((row, col)) = remaining.first_one() {
+            let cells = remaining.flood_fill(row, col, connectivity);
+            remaining.and_not_assign(&cells).unwrap();
+            components.push(cells);
+        }
+        components
+    }
+
+    #[rstest]
+    fn matches_flood_fill(
+        #[values(Connectivity::Four, Connectivity::Eight)] connectivity: Connectivity,
+        #[values(Boundary::Hard, Boundary::Wrap)] boundary: Boundary,
+        #[values((1, 1), (1, 9), (2, 2), (9, 1), (6, 7), (13, 70), (40, 3))] dims: (usize, usize),
+        #[values(0, 30, 55)] percent: usize,
+    ) {
+        let (n_rows, n_cols) = dims;
+        let mut board = BitBoardDyn::new(n_rows, n_cols).with_boundary(boundary);
+        for idx in 0..n_rows * n_cols {
+            let hash = (idx + percent).wrapping_mul(2_654_435_761) % 100;
+            board.board_mut().set(idx, hash >= percent);
+        }
+
+        let expected = flood_filled(&board, connectivity);
+        let found = board.connected_components(connectivity);
+        assert_eq!(found.len(), expected.len());
+        let labels = board.component_labels(connectivity);
+        for (label, (component, cells)) in found.iter().zip(&expected).enumerate() {
+            assert_eq!(component.size, cells.count_ones());
+            assert_eq!(Some(component.bounding_box), cells.bounding_box());
+            let mut uncropped = BitBoardDyn::new(n_rows, n_cols).with_boundary(boundary);
+            let bbox = component.bounding_box;
+            uncropped.blit(
+                &component.cells,
+                bbox.min_row as isize,
+                bbox.min_col as isize,
+                BlitMode::Copy,
+            );
+            assert_eq!(&uncropped, cells);
+            for (row, col) in cells.iter_ones() {
+                assert_eq!(labels[board.index_of(row, col)], Some(label));
+            }
+        }
+        assert_eq!(
+            labels.iter().filter(|label| label.is_some()).count(),
+            board.count_ones()
+        );
+    }
+
+    #[test]
+    fn static_checkerboard_has_a_component_per_cell() {
+        let mut board = BitBoardStatic::<4>::new(16, 16);
+        for idx in (0..256).filter(|idx| (idx / 16 + idx % 16) % 2 == 0) {
+            board.board_mut().set(idx, true);
+        }
+        assert_eq!(board.connected_components(Connectivity::Four).len(), 128);
+        assert_eq!(board.connected_components(Connectivity::Eight).len(), 1);
+        let component = &board.connected_components(Connectivity::Four)[1];
+        assert_eq!(component.cells.n_rows(), 1);
+        assert_eq!(component.cells.n_cols(), 1);
+    }
+}
@@ -0,0 +1,239 @@
+//! Cell coordinates, the directions between neighboring cells, and rectangles of
+//! cells.
+
+use std::fmt;
+
+/// The (row, col) position of a cell on a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    /// Create the coordinate of the cell at [row, col].
+    #[must_use]
+    pub fn new(row: usize, col: usize) -> Self {
+        Coord { row, col }
+    }
+
+    /// Returns the coordinate `d_row` rows down and `d_col` columns right of this one,
+    /// or `None` if either part would be negative or overflow.
+    #[must_use]
+    pub fn offset_by(self, d_row: isize, d_col: isize) -> Option<Coord> {
+        Some(Coord {
+            row: self.row.checked_add_signed(d_row)?,
+            col: self.col.checked_add_signed(d_col)?,
+        })
+    }
+
+    /// Returns the neighboring coordinate in `direction`, or `None` if it would be
+    /// negative.
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Option<Coord> {
+        let (d_row, d_col) = direction.offset();
+        self.offset_by(d_row, d_col)
+    }
+
+    /// Returns the neighboring coordinate in `direction`, or `None` if it is not on a
+    /// board of `n_rows` x `n_cols`.
+    #[must_use]
+    pub fn step_within(self, direction: Direction, n_rows: usize, n_cols: usize) -> Option<Coord> {
+        self.step(direction)
+            .filter(|next| next.row < n_rows && next.col < n_cols)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((row, col): (usize, usize)) -> Self {
+        Coord { row, col }
+    }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(coord: Coord) -> Self {
+        (coord.row, coord.col)
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// An inclusive rectangle of cells, such as the smallest one holding every set cell of
+/// a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min_row: usize,
+    pub min_col: usize,
+    pub max_row: usize,
+    pub max_col: usize,
+}
+
+impl BoundingBox {
+    /// Returns the number of rows in the box.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.max_row - self.min_row + 1
+    }
+
+    /// Returns the number of columns in the box.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.max_col - self.min_col + 1
+    }
+
+    /// Returns true if the cell at [row, col] is inside the box.
+    #[must_use]
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        (self.min_row..=self.max_row).contains(&row) && (self.min_col..=self.max_col).contains(&col)
+    }
+}
+
+/// One of the eight directions from a cell to its neighbors. North is towards row 0,
+/// and west is towards column 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Direction {
+    /// The four directions to the neighbors set by `set_cardinal_neighbors`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    /// The four directions to the neighbors set by `set_diagonals`.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthWest,
+        Direction::NorthEast,
+        Direction::SouthWest,
+        Direction::SouthEast,
+    ];
+
+    /// All eight directions, cardinal first.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+        Direction::NorthWest,
+        Direction::NorthEast,
+        Direction::SouthWest,
+        Direction::SouthEast,
+    ];
+
+    /// Returns the `(d_row, d_col)` needed to move one cell in this direction.
+    #[must_use]
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::South => (1, 0),
+            Direction::East => (0, 1),
+            Direction::West => (0, -1),
+            Direction::NorthEast => (-1, 1),
+            Direction::NorthWest => (-1, -1),
+            Direction::SouthEast => (1, 1),
+            Direction::SouthWest => (1, -1),
+        }
+    }
+
+    /// Returns the direction pointing the other way.
+    #[must_use]
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::NorthWest => Direction::SouthEast,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::SouthWest => Direction::NorthEast,
+        }
+    }
+
+    /// Returns true for the diagonal directions.
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        let (d_row, d_col) = self.offset();
+        d_row != 0 && d_col != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Direction::North, Some(Coord::new(0, 1)))]
+    #[case(Direction::South, None)]
+    #[case(Direction::East, Some(Coord::new(1, 2)))]
+    #[case(Direction::West, Some(Coord::new(1, 0)))]
+    #[case(Direction::NorthEast, Some(Coord::new(0, 2)))]
+    #[case(Direction::NorthWest, Some(Coord::new(0, 0)))]
+    #[case(Direction::SouthEast, None)]
+    #[case(Direction::SouthWest, None)]
+    fn step_within_2x3(#[case] direction: Direction, #[case] expected: Option<Coord>) {
+        assert_eq!(Coord::new(1, 1).step_within(direction, 2, 3), expected);
+    }
+
+    #[test]
+    fn step_stops_at_zero() {
+        let origin = Coord::new(0, 0);
+        assert_eq!(origin.step(Direction::North), None);
+        assert_eq!(origin.step(Direction::West), None);
+        assert_eq!(origin.step(Direction::SouthEast), Some(Coord::new(1, 1)));
+        assert_eq!(Coord::new(5, 5).offset_by(-5, -6), None);
+        assert_eq!(Coord::new(5, 5).offset_by(-5, 2), Some(Coord::new(0, 7)));
+    }
+
+    #[test]
+    fn opposite_undoes_step() {
+        let start = Coord::new(3, 3);
+        for direction in Direction::ALL {
+            let there = start.step(direction).unwrap();
+            assert_eq!(there.step(direction.opposite()), Some(start));
+            assert_eq!(
+                direction.is_diagonal(),
+                Direction::DIAGONAL.contains(&direction)
+            );
+        }
+    }
+
+    #[test]
+    fn bounding_box_size() {
+        let bbox = BoundingBox {
+            min_row: 1,
+            min_col: 2,
+            max_row: 1,
+            max_col: 5,
+        };
+        assert_eq!((bbox.height(), bbox.width()), (1, 4));
+        assert!(bbox.contains(1, 2));
+        assert!(bbox.contains(1, 5));
+        assert!(!bbox.contains(0, 3));
+        assert!(!bbox.contains(1, 6));
+    }
+
+    #[test]
+    fn tuple_conversions() {
+        let coord: Coord = (2, 7).into();
+        assert_eq!(coord, Coord::new(2, 7));
+        assert_eq!(<(usize, usize)>::from(coord), (2, 7));
+        assert_eq!(coord.to_string(), "(2, 7)");
+    }
+}
@@ -0,0 +1,251 @@
+//! Distance transforms: the distance from every cell to the nearest set cell.
+
+use crate::bitboard::{BitBoard, Boundary, Connectivity, grown};
+
+/// How the distance between two cells is measured by [`BitBoard::distance_transform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// The number of rows plus the number of columns apart: the number of steps to get
+    /// there moving only up, down, left, and right.
+    Manhattan,
+
+    /// The larger of the number of rows and the number of columns apart: the number of
+    /// steps to get there when diagonal steps are allowed.
+    Chebyshev,
+
+    /// The square of the straight-line distance, which stays an exact whole number.
+    /// Take the square root for the distance itself.
+    SquaredEuclidean,
+}
+
+/// Marks a cell with no set cell to measure to.
+const UNREACHED: u64 = u64::MAX;
+
+/// Returns the Manhattan or Chebyshev distance from each cell to the nearest set cell,
+/// by growing the set cells one step at a time and recording when each cell is
+/// first covered.
+pub(crate) fn grown_distances<B: BitBoard>(board: &B, connectivity: Connectivity) -> Vec<u32> {
+    let mut distances = vec![u32::MAX; board.n_rows() * board.n_cols()];
+    for idx in board.board().iter_ones() {
+        distances[idx] = 0;
+    }
+
+    let mut reached = board.clone();
+    let mut frontier = board.clone();
+    let mut distance = 0;
+    while frontier.board().any() {
+        frontier = grown(&frontier, connectivity);
+        // Keep only the cells that are new in this step
+        *frontier.board_mut() |= reached.board();
+        *frontier.board_mut() ^= reached.board();
+        distance += 1;
+        for idx in frontier.board().iter_ones() {
+            distances[idx] = distance;
+        }
+        *reached.board_mut() |= frontier.board();
+    }
+    distances
+}
+
+/// Returns the squared Euclidean distance from each cell to the nearest set cell, one
+/// dimension at a time: first down each column, then along each row using the column
+/// distances as a starting cost.
+pub(crate) fn squared_euclidean_distances<B: BitBoard>(board: &B) -> Vec<u32> {
+    let (n_rows, n_cols) = (board.n_rows(), board.n_cols());
+    let wrap = board.boundary() == Boundary::Wrap;
+
+    let mut costs: Vec<u64> = board
+        .board()
+        .iter()
+        .map(|cell| if *cell { 0 } else { UNREACHED })
+        .collect();
+    let mut line = Vec::with_capacity(n_rows.max(n_cols));
+    for col in 0..n_cols {
+        line.clear();
+        line.extend((0..n_rows).map(|row| costs[row * n_cols + col]));
+        for (row, cost) in squared_distances_1d(&line, wrap).into_iter().enumerate() {
+            costs[row * n_cols + col] = cost;
+        }
+    }
+    for row in costs.chunks_exact_mut(n_cols) {
+        let distances = squared_distances_1d(row, wrap);
+        row.copy_from_slice(&distances);
+    }
+
+    costs
+        .into_iter()
+        .map(|cost| u32::try_from(cost).unwrap_or(u32::MAX))
+        .collect()
+}
+
+/// Returns, for each position `q`, the smallest `(q - p)^2 + costs[p]` over all
+/// positions `p` that are not [`UNREACHED`]. This finds the lower envelope of the
+/// parabolas rooted at each position, as described by Felzenszwalb and Huttenlocher.
+/// If `wrap` is set, the line is treated as a ring.
+fn squared_distances_1d(costs: &[u64], wrap: bool) -> Vec<u64> {
+    let len = costs.len();
+    if wrap {
+        // Tiling the ring three times puts the nearest copy of every position within
+        // reach of the middle tile
+        let tiled: Vec<u64> = costs.iter().cycle().take(3 * len).copied().collect();
+        return squared_distances_1d(&tiled, false)[len..2 * len].to_vec();
+    }
+
+    // The parabolas on the envelope, and where each one starts to be the lowest
+    let mut roots: Vec<usize> = Vec::with_capacity(len);
+    let mut starts: Vec<f64> = Vec::with_capacity(len);
+    for (q, &cost) in costs.iter().enumerate() {
+        if cost == UNREACHED {
+            continue;
+        }
+        let height = cost as f64 + (q * q) as f64;
+        loop {
+            let Some(&p) = roots.last() else {
+                roots.push(q);
+                starts.push(f64::NEG_INFINITY);
+                break;
+            };
+            // Where the parabola at q meets the one at p
+            let p_height = costs[p] as f64 + (p * p) as f64;
+            let meet = (height - p_height) / (2.0 * (q - p) as f64);
+            if meet
+                <= *starts
+                    .last()
+                    .expect("starts and roots have the same length")
+            {
+                roots.pop();
+                starts.pop();
+            } else {
+                roots.push(q);
+                starts.push(meet);
+                break;
+            }
+        }
+    }
+
+    let mut distances = vec![UNREACHED; len];
+    if roots.is_empty() {
+        return distances;
+    }
+    let mut k = 0;
+    for (q, distance) in distances.iter_mut().enumerate() {
+        while k + 1 < roots.len() && starts[k + 1] < q as f64 {
+            k += 1;
+        }
+        let p = roots[k];
+        *distance = (q.abs_diff(p) * q.abs_diff(p)) as u64 + costs[p];
+    }
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitboarddyn::BitBoardDyn, bitboardstatic::BitBoardStatic};
+    use rstest::rstest;
+
+    /// Measures every distance directly, taking the short way around a wrapping board.
+    fn brute_force(board: &BitBoardDyn, metric: Metric) -> Vec<u32> {
+        let (n_rows, n_cols) = (board.n_rows(), board.n_cols());
+        let wrap = board.boundary() == Boundary::Wrap;
+        let apart = |a: usize, b: usize, len: usize| {
+            let d = a.abs_diff(b);
+            if wrap { d.min(len - d) } else { d }
+        };
+        (0..n_rows * n_cols)
+            .map(|idx| {
+                let (row, col) = board.row_col_of(idx);
+                board
+                    .iter_ones()
+                    .map(|(r, c)| {
+                        let (d_row, d_col) = (apart(row, r, n_rows), apart(col, c, n_cols));
+                        let distance = match metric {
+                            Metric::Manhattan => d_row + d_col,
+                            Metric::Chebyshev => d_row.max(d_col),
+                            Metric::SquaredEuclidean => d_row * d_row + d_col * d_col,
+                        };
+                        distance as u32
+                    })
+                    .min()
+                    .unwrap_or(u32::MAX)
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn matches_brute_force(
+        #[values(Metric::Manhattan, Metric::Chebyshev, Metric::SquaredEuclidean)] metric: Metric,
+        #[values(Boundary::Hard, Boundary::Wrap)] boundary: Boundary,
+        #[values((1, 1), (7, 9), (11, 75), (40, 3))] dims: (usize, usize),
+    ) {
+        let (n_rows, n_cols) = dims;
+        let mut board = BitBoardDyn::new(n_rows, n_cols).with_boundary(boundary);
+        for idx in 0..n_rows * n_cols {
+            board
+                .board_mut()
+                .set(idx, idx.wrapping_mul(2_654_435_761) % 100 < 4);
+        }
+        assert_eq!(
+            board.distance_transform(metric),
+            brute_force(&board, metric)
+        );
+        let inverted = board.not();
+        assert_eq!(
+            inverted.distance_transform(metric),
+            brute_force(&inverted, metric)
+        );
+    }
+
+    #[test]
+    fn single_cell_distances() {
+        let board = BitBoardStatic::<1>::from_coords(3, 4, [(1, 1)]).unwrap();
+        assert_eq!(
+            board.distance_transform(Metric::Manhattan),
+            vec![2, 1, 2, 3, 1, 0, 1, 2, 2, 1, 2, 3]
+        );
+        assert_eq!(
+            board.distance_transform(Metric::Chebyshev),
+            vec![1, 1, 1, 2, 1, 0, 1, 2, 1, 1, 1, 2]
+        );
+        assert_eq!(
+            board.distance_transform(Metric::SquaredEuclidean),
+            vec![2, 1, 2, 5, 1, 0, 1, 4, 2, 1, 2, 5]
+        );
+        assert_eq!(
+            BitBoardStatic::<1>::new(2, 2).distance_transform(Metric::SquaredEuclidean),
+            vec![u32::MAX; 4]
+        );
+    }
+
+    #[test]
+    fn squared_distances_on_a_line() {
+        let costs = [UNREACHED, 0, UNREACHED, UNREACHED, UNREACHED, UNREACHED, 0];
+        assert_eq!(
+            squared_distances_1d(&costs, false),
+            vec![1, 0, 1, 4, 4, 1, 0]
+        );
+        // Every cell is already closest to a set cell without going around the ring
+        assert_eq!(
+            squared_distances_1d(&costs, true),
+            vec![1, 0, 1, 4, 4, 1, 0]
+        );
+        assert_eq!(
+            squared_distances_1d(&[UNREACHED, UNREACHED, UNREACHED, 0, UNREACHED], true),
+            vec![4, 4, 1, 0, 1]
+        );
+        assert_eq!(
+            squared_distances_1d(&[UNREACHED; 3], false),
+            vec![UNREACHED; 3]
+        );
+    }
+
+    #[test]
+    fn starting_costs_are_kept() {
+        // A position's own cost wins when the neighbors are too expensive
+        assert_eq!(squared_distances_1d(&[9, 2, 9], false), vec![3, 2, 3]);
+        assert_eq!(
+            squared_distances_1d(&[0, 50, 50, 50], false),
+            vec![0, 1, 4, 9]
+        );
+    }
+}
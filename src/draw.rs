@@ -0,0 +1,741 @@
+//! Rasterization of lines and shapes onto a board, for [`BitBoard::draw_line`] and its
+//! neighbors. Points are `(row, col)` and may lie off the board: whatever falls off the
+//! edges is clipped, even on a wrapping board.
+
+use crate::bitboard::BitBoard;
+use std::ops::Range;
+
+/// Returns the cells on the Bresenham line from `from` to `to`, as `(row, col)`,
+/// including both ends.
+pub(crate) fn bresenham(from: (isize, isize), to: (isize, isize)) -> Vec<(isize, isize)> {
+    let (mut row, mut col) = from;
+    let d_col = (to.1 - col).abs();
+    let d_row = -(to.0 - row).abs();
+    let (step_row, step_col) = ((to.0 - row).signum(), (to.1 - col).signum());
+    let mut err = d_col + d_row;
+
+    let mut cells = Vec::with_capacity(d_col.max(-d_row) as usize + 1);
+    loop {
+        cells.push((row, col));
+        if (row, col) == to {
+            return cells;
+        }
+        let e2 = 2 * err;
+        if e2 >= d_row {
+            err += d_row;
+            col += step_col;
+        }
+        if e2 <= d_col {
+            err += d_col;
+            row += step_row;
+        }
+    }
+}
+
+/// Calls `plot` with each cell of the Bresenham line from `from` to `to` that is on an
+/// `n_rows` x `n_cols` board. The line steps one cell at a time along its longer axis,
+/// and only the steps that cross the board are visited, so far away ends cost nothing.
+/// The cell on the shorter axis is worked out directly for each step, rounding to the
+/// nearest and breaking ties towards `to`, which gives the same cells as
+/// [`bresenham`].
+pub(crate) fn clipped_line(
+    from: (isize, isize),
+    to: (isize, isize),
+    n_rows: usize,
+    n_cols: usize,
+    mut plot: impl FnMut(usize, usize),
+) {
+    // Wide enough that no difference or product below can overflow
+    let (from_row, from_col) = (from.0 as i128, from.1 as i128);
+    let (d_row, d_col) = (to.0 as i128 - from_row, to.1 as i128 - from_col);
+    let rows_major = d_row.abs() > d_col.abs();
+    let ((from_major, d_major, len_major), (from_minor, d_minor, len_minor)) = if rows_major {
+        ((from_row, d_row, n_rows), (from_col, d_col, n_cols))
+    } else {
+        ((from_col, d_col, n_cols), (from_row, d_row, n_rows))
+    };
+
+    let to_major = from_major + d_major;
+    let first = from_major.min(to_major).max(0);
+    let last = from_major.max(to_major).min(len_major as i128 - 1);
+    for major in first..=last {
+        let minor = if d_major == 0 {
+            from_minor
+        } else {
+            let (num, den) = (
+                (major - from_major).unsigned_abs() * d_minor.unsigned_abs(),
+                d_major.unsigned_abs(),
+            );
+            let offset = num / den + u128::from(2 * (num % den) >= den);
+            from_minor + d_minor.signum() * offset as i128
+        };
+        if (0..len_minor as i128).contains(&minor) {
+            let (row, col) = if rows_major {
+                (major, minor)
+            } else {
+                (minor, major)
+            };
+            plot(row as usize, col as usize);
+        }
+    }
+}
+
+/// Sets the cells of `row` from `first_col` to `last_col`, inclusive, to `value`,
+/// filling the part that is on the board as one range.
+pub(crate) fn fill_span<B: BitBoard>(
+    board: &mut B,
+    row: isize,
+    first_col: isize,
+    last_col: isize,
+    value: bool,
+) {
+    let n_cols = board.n_cols() as isize;
+    if !(0..board.n_rows() as isize).contains(&row) || last_col < 0 || first_col >= n_cols {
+        return;
+    }
+    let (first_col, last_col) = (first_col.max(0), last_col.min(n_cols - 1));
+    if first_col > last_col {
+        return;
+    }
+    let start = board.index_of(row as usize, first_col as usize);
+    let len = (last_col - first_col) as usize + 1;
+    board.board_mut()[start..start + len].fill(value);
+}
+
+/// Returns `a * b` as `(high, low)` halves of 256 bits, which compare as tuples in the
+/// same order as the products.
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const LOW: u128 = u64::MAX as u128;
+    let (a_high, a_low, b_high, b_low) = (a >> 64, a & LOW, b >> 64, b & LOW);
+    let (middle, middle_carry) = (a_high * b_low).overflowing_add(a_low * b_high);
+    let (low, low_carry) = (a_low * b_low).overflowing_add(middle << 64);
+    let high =
+        a_high * b_high + (middle >> 64) + (u128::from(middle_carry) << 64) + u128::from(low_carry);
+    (high, low)
+}
+
+/// Returns the largest `x` in `0..=max` for which `holds` is true, where `holds` is
+/// true up to some `x` and false after it, or `None` if it is never true.
+fn last_holding(max: u128, holds: impl Fn(u128) -> bool) -> Option<u128> {
+    if !holds(0) {
+        return None;
+    }
+    let (mut low, mut high) = (0, max);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if holds(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Some(low)
+}
+
+/// Returns the cells of a midpoint ellipse `d_row` rows from its center, as up to two
+/// runs `(inner, outer)` of column offsets from the center, on one side. The ellipse
+/// reaches `radius_rows` up and down and `radius_cols` left and right, and `d_row` must
+/// be at most `radius_rows`.
+///
+/// Where the curve is steep, the midpoint algorithm steps along the rows and picks the
+/// column for each, and where it is flat, it steps along the columns and picks the row
+/// for each. Rather than walking the curve, both choices are worked out for this row.
+fn ellipse_row(radius_rows: u128, radius_cols: u128, d_row: u128) -> [Option<(u128, u128)>; 2] {
+    let (a, b, d) = (radius_cols, radius_rows, d_row);
+    if a == 0 || b == 0 {
+        return [Some((0, a)), None];
+    }
+    let (a2, b2) = (a * a, b * b);
+    // Where it is steep, the outermost column with b² d_col (d_col - 1) + a² d_row² <
+    // a² b², which is the midpoint test
+    let steep = last_holding(a, |x| {
+        wide_mul(b2, x * x.saturating_sub(1)) < wide_mul(a2, b2 - d * d)
+    })
+    .filter(|&x| wide_mul(a2, d) <= wide_mul(b2, x))
+    .map(|x| (x, x));
+    // Where it is flat, the columns whose outermost row with a² d_row (d_row - 1) +
+    // b² d_col² < a² b² is this one
+    let reaches = |d: u128, x: u128| wide_mul(b2, x * x) < wide_mul(a2, b2 - d * d + d);
+    let inner = if d < b {
+        last_holding(a, |x| reaches(d + 1, x)).map_or(0, |x| x + 1)
+    } else {
+        0
+    };
+    let flat = last_holding(a, |x| reaches(d, x) && wide_mul(b2, x) <= wide_mul(a2, d))
+        .filter(|&outer| inner <= outer)
+        .map(|outer| (inner, outer));
+    [steep, flat]
+}
+
+/// Calls `span` with each row of an `n_rows` x `n_cols` board that the midpoint ellipse
+/// around `center` crosses, and each range of columns of the row to set: the outline,
+/// or if `filled`, everything from its left edge to its right. Only the rows the
+/// ellipse covers on the board are visited, so a huge ellipse costs no more than the
+/// board does.
+pub(crate) fn ellipse_spans(
+    center: (isize, isize),
+    radius_rows: usize,
+    radius_cols: usize,
+    n_rows: usize,
+    n_cols: usize,
+    filled: bool,
+    mut span: impl FnMut(usize, Range<usize>),
+) {
+    // Wide enough that no sum or difference below can overflow
+    let (row, col) = (center.0 as i128, center.1 as i128);
+    let (radius_rows, radius_cols) = (radius_rows as u128, radius_cols as u128);
+    let first = (row - radius_rows as i128).max(0);
+    let last = (row + radius_rows as i128).min(n_rows as i128 - 1);
+    let clip = |first_col: i128, last_col: i128| {
+        let (first_col, last_col) = (first_col.max(0), last_col.min(n_cols as i128 - 1));
+        (first_col <= last_col).then(|| first_col as usize..last_col as usize + 1)
+    };
+    for board_row in first..=last {
+        let d_row = (board_row - row).unsigned_abs();
+        for (inner, outer) in ellipse_row(radius_rows, radius_cols, d_row)
+            .into_iter()
+            .flatten()
+        {
+            let inner = if filled { 0 } else { inner as i128 };
+            let outer = outer as i128;
+            let right = clip(col + inner, col + outer);
+            let left = clip(col - outer, col - inner);
+            for cols in right.into_iter().chain(left) {
+                span(board_row as usize, cols);
+            }
+        }
+    }
+}
+
+/// Sets every cell whose center is inside the polygon to `value`, one row at a time,
+/// using the even-odd rule. Edges are left to the caller.
+pub(crate) fn fill_polygon_interior<B: BitBoard>(
+    board: &mut B,
+    vertices: &[(isize, isize)],
+    value: bool,
+) {
+    let Some(top) = vertices.iter().map(|&(row, _)| row).min() else {
+        return;
+    };
+    let bottom = vertices.iter().map(|&(row, _)| row).max().unwrap_or(top);
+    let (top, bottom) = (top.max(0), bottom.min(board.n_rows() as isize - 1));
+
+    // Each crossing is kept exactly, as the column at or left of it and whether it
+    // falls strictly between two columns. Crossings between the same two columns
+    // sort in any order, as no cell center lies between them.
+    let mut crossings: Vec<(i128, bool)> = Vec::with_capacity(vertices.len());
+    for row in top..=bottom {
+        crossings.clear();
+        let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+        for (&(row_a, col_a), &(row_b, col_b)) in edges {
+            // Count each edge over [upper, lower), so that a vertex shared by two edges
+            // is only crossed once, and horizontal edges not at all
+            let ((upper, upper_col), (lower, lower_col)) = if row_a <= row_b {
+                ((row_a, col_a), (row_b, col_b))
+            } else {
+                ((row_b, col_b), (row_a, col_a))
+            };
+            if (upper..lower).contains(&row) {
+                // Wide enough that no difference or product below can overflow
+                let d_col = lower_col as i128 - upper_col as i128;
+                let (num, den) = (
+                    (row as i128 - upper as i128).unsigned_abs() * d_col.unsigned_abs(),
+                    (lower as i128 - upper as i128).unsigned_abs(),
+                );
+                let (offset, between) = ((num / den) as i128, num % den != 0);
+                let floor = if d_col < 0 {
+                    upper_col as i128 - offset - i128::from(between)
+                } else {
+                    upper_col as i128 + offset
+                };
+                crossings.push((floor, between));
+            }
+        }
+        crossings.sort_unstable();
+        for pair in crossings.chunks_exact(2) {
+            let ((left, left_between), (right, _)) = (pair[0], pair[1]);
+            // Both lie between the columns of the edge's ends, so they fit an isize
+            fill_span(
+                board,
+                row,
+                (left + i128::from(left_between)) as isize,
+                right as isize,
+                value,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bitboard::{Boundary, Connectivity},
+        bitboarddyn::BitBoardDyn,
+        bitboardstatic::BitBoardStatic,
+        parse::picture,
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    #[case((0, 0), (0, 3), vec![(0, 0), (0, 1), (0, 2), (0, 3)])]
+    #[case((0, 0), (2, 4), vec![(0, 0), (1, 1), (1, 2), (2, 3), (2, 4)])]
+    #[case((3, 1), (0, 0), vec![(3, 1), (2, 1), (1, 0), (0, 0)])]
+    #[case((2, 2), (2, 2), vec![(2, 2)])]
+    fn bresenham_lines(
+        #[case] from: (isize, isize),
+        #[case] to: (isize, isize),
+        #[case] expected: Vec<(isize, isize)>,
+    ) {
+        assert_eq!(bresenham(from, to), expected);
+    }
+
+    #[test]
+    fn clipped_line_matches_bresenham() {
+        let (n_rows, n_cols) = (5, 6);
+        let ends: Vec<(isize, isize)> = (-3..9)
+            .flat_map(|row| (-3..9).map(move |col| (row, col)))
+            .collect();
+        for &from in &ends {
+            for &to in &ends {
+                let mut expected: Vec<(usize, usize)> = bresenham(from, to)
+                    .into_iter()
+                    .filter(|&(row, col)| {
+                        (0..n_rows as isize).contains(&row) && (0..n_cols as isize).contains(&col)
+                    })
+                    .map(|(row, col)| (row as usize, col as usize))
+                    .collect();
+                let mut found = Vec::new();
+                clipped_line(from, to, n_rows, n_cols, |row, col| found.push((row, col)));
+                expected.sort_unstable();
+                found.sort_unstable();
+                assert_eq!(found, expected, "{from:?} to {to:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn far_off_lines_and_rects() {
+        let mut board = BitBoardDyn::new(4, 4);
+        board.draw_line((isize::MIN, 1), (isize::MAX, 1), true);
+        board.draw_line((2, isize::MIN), (2, isize::MAX), true);
+        board.draw_line((isize::MIN, isize::MIN), (isize::MAX, isize::MAX), true);
+        board.draw_rect(isize::MIN, isize::MIN, usize::MAX, usize::MAX, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ##..
+                .#..
+                ####
+                .#.#
+            "
+            )
+        );
+        board.draw_rect(isize::MAX, 0, usize::MAX, 2, true);
+        board.draw_rect(-1_000_000_000, 3, 2_000_000_000, 1, false);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ##..
+                .#..
+                ###.
+                .#..
+            "
+            )
+        );
+    }
+
+    #[test]
+    fn lines_are_clipped() {
+        let mut board = BitBoardStatic::<1>::new(4, 5).with_boundary(Boundary::Wrap);
+        board.draw_line((-2, -2), (5, 5), true);
+        assert_eq!(
+            board.iter_ones().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2), (3, 3)]
+        );
+        board.draw_line((2, 0), (2, 9), true);
+        board.draw_line((1, 1), (3, 1), false);
+        assert_eq!(
+            board,
+            BitBoardStatic::<1>::from_coords(
+                4,
+                5,
+                [(0, 0), (2, 0), (2, 2), (2, 3), (2, 4), (3, 3)]
+            )
+            .unwrap()
+            .with_boundary(Boundary::Wrap)
+        );
+    }
+
+    #[test]
+    fn rects() {
+        let mut board = BitBoardDyn::new(5, 7);
+        board.draw_rect(1, 1, 3, 5, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                .......
+                .#####.
+                .#...#.
+                .#####.
+                .......
+            "
+            )
+        );
+        board.fill_rect(-1, 4, 3, 10, true);
+        board.fill_rect(2, 2, 1, 1, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ....###
+                .######
+                .##..#.
+                .#####.
+                .......
+            "
+            )
+        );
+        // Zero-sized and fully off-board rectangles draw nothing
+        let before = board.clone();
+        board.draw_rect(0, 0, 0, 3, false);
+        board.fill_rect(2, 0, 4, 0, false);
+        board.fill_rect(-5, -5, 3, 3, false);
+        board.draw_rect(5, 0, 2, 2, false);
+        assert_eq!(board, before);
+    }
+
+    #[test]
+    fn rect_outline_clipped() {
+        let mut board = BitBoardDyn::new(4, 4);
+        board.draw_rect(-1, 1, 6, 2, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                .##.
+                .##.
+                .##.
+                .##.
+            "
+            )
+        );
+    }
+
+    #[test]
+    fn circles() {
+        let mut board = BitBoardDyn::new(7, 7);
+        board.draw_circle((3, 3), 3, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ..###..
+                .#...#.
+                #.....#
+                #.....#
+                #.....#
+                .#...#.
+                ..###..
+            "
+            )
+        );
+
+        let mut filled = BitBoardDyn::new(7, 7);
+        filled.fill_circle((3, 3), 3, true);
+        assert_eq!(
+            filled,
+            picture(
+                "
+                ..###..
+                .#####.
+                #######
+                #######
+                #######
+                .#####.
+                ..###..
+            "
+            )
+        );
+        // The fill covers exactly the outline and everything inside it
+        assert_eq!(filled.and(&board), Ok(board));
+    }
+
+    #[test]
+    fn small_and_clipped_circles() {
+        let mut board = BitBoardDyn::new(3, 3);
+        board.draw_circle((1, 1), 0, true);
+        assert_eq!(board.iter_ones().collect::<Vec<_>>(), vec![(1, 1)]);
+        board.draw_circle((0, 0), 1, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                .#.
+                ##.
+                ...
+            "
+            )
+        );
+        let mut filled = BitBoardDyn::new(3, 3);
+        filled.fill_circle((-1, 3), 2, true);
+        assert_eq!(
+            filled,
+            picture(
+                "
+                .##
+                ..#
+                ...
+            "
+            )
+        );
+    }
+
+    #[test]
+    fn ellipses() {
+        let mut board = BitBoardDyn::new(5, 9);
+        board.draw_ellipse((2, 4), 2, 4, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ..#####..
+                .#.....#.
+                #.......#
+                .#.....#.
+                ..#####..
+            "
+            )
+        );
+        // An ellipse with equal radii is a circle
+        let (mut ellipse, mut circle) = (BitBoardDyn::new(11, 11), BitBoardDyn::new(11, 11));
+        ellipse.draw_ellipse((5, 5), 5, 5, true);
+        circle.draw_circle((5, 5), 5, true);
+        assert_eq!(ellipse, circle);
+    }
+
+    #[test]
+    fn flat_ellipses_are_lines() {
+        let mut board = BitBoardDyn::new(3, 5);
+        board.draw_ellipse((1, 2), 0, 2, true);
+        board.draw_ellipse((1, 4), 1, 0, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ....#
+                #####
+                ....#
+            "
+            )
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        let mut board = BitBoardDyn::new(5, 7);
+        board.fill_polygon(&[(0, 3), (3, 0), (3, 6)], true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ...#...
+                ..###..
+                .#####.
+                #######
+                .......
+            "
+            )
+        );
+
+        // A concave polygon leaves its notch empty
+        let mut board = BitBoardDyn::new(5, 5);
+        board.fill_polygon(
+            &[
+                (0, 0),
+                (0, 4),
+                (4, 4),
+                (4, 3),
+                (1, 3),
+                (1, 1),
+                (4, 1),
+                (4, 0),
+            ],
+            true,
+        );
+        assert_eq!(
+            board,
+            picture(
+                "
+                #####
+                #####
+                ##.##
+                ##.##
+                ##.##
+            "
+            )
+        );
+    }
+
+    #[test]
+    fn polygons_are_clipped() {
+        let mut board = BitBoardDyn::new(4, 4);
+        board.fill_polygon(&[(-10, -10), (-10, 10), (10, 10), (10, -10)], true);
+        assert_eq!(board.count_ones(), 16);
+
+        let mut board = BitBoardDyn::new(3, 3);
+        board.fill_polygon(&[], true);
+        board.fill_polygon(&[(1, 1)], true);
+        board.fill_polygon(&[(2, 0), (2, 5)], true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ...
+                .#.
+                ###
+            "
+            )
+        );
+    }
+
+    #[test]
+    fn far_off_polygons() {
+        let mut board = BitBoardDyn::new(4, 5);
+        board.fill_polygon(&[(isize::MIN, 0), (isize::MAX, 0), (0, 3)], true);
+        board.fill_polygon(&[(isize::MIN, isize::MAX), (isize::MAX, isize::MIN)], true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ####.
+                ####.
+                ####.
+                ####.
+            "
+            )
+        );
+    }
+
+    /// The cells of the midpoint circle of `radius`, as offsets from its center, found
+    /// by walking one octant.
+    fn walked_circle(radius: isize) -> Vec<(isize, isize)> {
+        let mut cells = Vec::new();
+        let (mut x, mut y) = (radius, 0);
+        let mut err = 1 - x;
+        while x >= y {
+            for (d_row, d_col) in [(y, x), (x, y)] {
+                cells.extend([
+                    (d_row, d_col),
+                    (d_row, -d_col),
+                    (-d_row, d_col),
+                    (-d_row, -d_col),
+                ]);
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn circles_match_walked_circles() {
+        for radius in 0..40 {
+            let cells = walked_circle(radius);
+            // Centered, and cut by every edge
+            for center in [
+                (radius + 1, radius + 1),
+                (2, 3),
+                (radius, -2),
+                (-1, 2 * radius),
+            ] {
+                let n = 2 * radius as usize + 3;
+                let (mut drawn, mut filled) = (BitBoardDyn::new(n, n), BitBoardDyn::new(n, n));
+                drawn.draw_circle(center, radius as usize, true);
+                filled.fill_circle(center, radius as usize, true);
+                let (mut expected_drawn, mut expected_filled) =
+                    (BitBoardDyn::new(n, n), BitBoardDyn::new(n, n));
+                for &(d_row, d_col) in &cells {
+                    let row = center.0 + d_row;
+                    let col = center.1 + d_col;
+                    fill_span(&mut expected_drawn, row, col, col, true);
+                    fill_span(
+                        &mut expected_filled,
+                        row,
+                        center.1 - d_col,
+                        center.1 + d_col,
+                        true,
+                    );
+                }
+                assert_eq!(drawn, expected_drawn, "radius {radius} at {center:?}");
+                assert_eq!(filled, expected_filled, "radius {radius} at {center:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn ellipses_are_thin_symmetric_and_reach_their_radii() {
+        for radius_rows in 1..16 {
+            for radius_cols in 1..16 {
+                let n = 2 * radius_rows.max(radius_cols) + 1;
+                let center = n / 2;
+                let mut board = BitBoardDyn::new(n, n);
+                board.draw_ellipse(
+                    (center as isize, center as isize),
+                    radius_rows,
+                    radius_cols,
+                    true,
+                );
+                let mut swapped = BitBoardDyn::new(n, n);
+                swapped.draw_ellipse(
+                    (center as isize, center as isize),
+                    radius_cols,
+                    radius_rows,
+                    true,
+                );
+                assert_eq!(board.transpose(), swapped);
+                assert_eq!(board.flip_vertical(), board);
+                assert_eq!(board.flip_horizontal(), board);
+                assert!(board.get(center, center + radius_cols));
+                assert!(board.get(center + radius_rows, center));
+                assert_eq!(board.connected_components(Connectivity::Eight).len(), 1);
+                let thick = (0..n - 1).any(|row| {
+                    (0..n - 1).any(|col| board.sub_board(row, col, 2, 2).count_ones() == 4)
+                });
+                assert!(!thick, "{radius_rows} x {radius_cols}\n{board}");
+            }
+        }
+    }
+
+    #[test]
+    fn far_off_and_huge_circles() {
+        let mut board = BitBoardDyn::new(4, 4);
+        board.draw_circle((isize::MAX, 0), 2, true);
+        board.draw_circle((isize::MIN, isize::MAX), usize::MAX, true);
+        board.draw_ellipse((0, 0), 1 << 40, 3, true);
+        board.draw_circle((200_000_000, 1), 200_000_000, true);
+        assert_eq!(
+            board,
+            picture(
+                "
+                ####
+                ...#
+                ...#
+                ...#
+            "
+            )
+        );
+        board.fill_circle((isize::MIN, isize::MIN), usize::MAX, true);
+        assert_eq!(board.count_ones(), 16);
+        board.draw_ellipse((isize::MAX, isize::MAX), usize::MAX, usize::MAX, false);
+        assert_eq!(board.count_ones(), 16);
+    }
+}
@@ -0,0 +1,298 @@
+//! Field of view and line of sight across a board whose set cells are opaque, such as
+//! the walls of a roguelike map.
+
+use crate::bitboard::BitBoard;
+use crate::coord::Coord;
+
+/// How [`BitBoard::field_of_view`] decides which cells can be seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FovAlgorithm {
+    /// Symmetric shadowcasting, which sweeps each quadrant a row at a time and tracks
+    /// the shadows cast by opaque cells. A cell is visible from the origin exactly when
+    /// the origin would be visible from it, and walls are seen without gaps.
+    SymmetricShadowcasting,
+
+    /// Casts a Bresenham line to each cell, as [`BitBoard::has_line_of_sight`] does.
+    /// Simpler, but slower and not symmetric.
+    RayCasting,
+}
+
+/// A fraction `num / den` with a positive denominator.
+#[derive(Debug, Clone, Copy)]
+struct Slope {
+    num: i64,
+    den: i64,
+}
+
+/// One row of a quadrant being scanned: the cells `depth` away from the origin that
+/// lie between the two slopes.
+#[derive(Debug, Clone, Copy)]
+struct ScanRow {
+    depth: i64,
+    start: Slope,
+    end: Slope,
+}
+
+impl ScanRow {
+    /// Returns the first and last column of the row, rounding the start up and the end
+    /// down when they fall exactly halfway between two columns.
+    fn cols(&self) -> (i64, i64) {
+        let first =
+            (2 * self.depth * self.start.num + self.start.den).div_euclid(2 * self.start.den);
+        let last = -(self.end.den - 2 * self.depth * self.end.num).div_euclid(2 * self.end.den);
+        (first, last)
+    }
+
+    /// Returns true if the center of `col` is within the slopes, so that the cell can
+    /// see the origin as well as be seen from it.
+    fn is_symmetric(&self, col: i64) -> bool {
+        col * self.start.den >= self.depth * self.start.num
+            && col * self.end.den <= self.depth * self.end.num
+    }
+
+    fn next(&self) -> ScanRow {
+        ScanRow {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+}
+
+/// Returns the slope to the edge of a cell closest to the start of its row.
+fn slope(depth: i64, col: i64) -> Slope {
+    Slope {
+        num: 2 * col - 1,
+        den: 2 * depth,
+    }
+}
+
+/// Maps a (depth, col) position within a quadrant to a (d_row, d_col) offset from the
+/// origin.
+type QuadrantTransform = fn(i64, i64) -> (i64, i64);
+
+/// Returns the cells visible from `origin` by symmetric shadowcasting, as described by
+/// Albert Ford. Cells past the edge of the board are treated as opaque.
+pub(crate) fn shadowcast<B: BitBoard>(board: &B, origin: Coord, radius: usize) -> B {
+    let mut visible = board.empty_with_dims(board.n_rows(), board.n_cols());
+    visible.set(origin.row, origin.col, true);
+    let (n_rows, n_cols) = (board.n_rows() as i64, board.n_cols() as i64);
+    let (origin_row, origin_col) = (origin.row as i64, origin.col as i64);
+    // No cell is further away than this, and clamping keeps `radius * radius` in range
+    let radius = i64::try_from(radius).map_or(n_rows + n_cols, |r| r.min(n_rows + n_cols));
+    let max_depth = radius.min(n_rows.max(n_cols));
+
+    let quadrants: [QuadrantTransform; 4] = [
+        |depth, col| (-depth, col),
+        |depth, col| (depth, col),
+        |depth, col| (col, depth),
+        |depth, col| (col, -depth),
+    ];
+    for transform in quadrants {
+        let cell = |depth: i64, col: i64| {
+            let (d_row, d_col) = transform(depth, col);
+            let (row, col) = (origin_row + d_row, origin_col + d_col);
+            ((0..n_rows).contains(&row) && (0..n_cols).contains(&col))
+                .then_some((row as usize, col as usize))
+        };
+        let is_wall = |depth: i64, col: i64| cell(depth, col).is_none_or(|(r, c)| board.get(r, c));
+
+        let mut rows = vec![ScanRow {
+            depth: 1,
+            start: Slope { num: -1, den: 1 },
+            end: Slope { num: 1, den: 1 },
+        }];
+        while let Some(mut row) = rows.pop() {
+            if row.depth > max_depth {
+                continue;
+            }
+            let (first, last) = row.cols();
+            let mut prev_wall = None;
+            for col in first..=last {
+                let wall = is_wall(row.depth, col);
+                if (wall || row.is_symmetric(col))
+                    && row.depth * row.depth + col * col <= radius * radius
+                    && let Some((r, c)) = cell(row.depth, col)
+                {
+                    visible.set(r, c, true);
+                }
+                if prev_wall == Some(true) && !wall {
+                    row.start = slope(row.depth, col);
+                }
+                if prev_wall == Some(false) && wall {
+                    rows.push(ScanRow {
+                        end: slope(row.depth, col),
+                        ..row.next()
+                    });
+                }
+                prev_wall = Some(wall);
+            }
+            if prev_wall == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bitboard::Boundary, bitboarddyn::BitBoardDyn, bitboardstatic::BitBoardStatic,
+        parse::picture,
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    fn open_room_sees_radius(
+        #[values(FovAlgorithm::SymmetricShadowcasting, FovAlgorithm::RayCasting)]
+        algorithm: FovAlgorithm,
+    ) {
+        let room = BitBoardDyn::new(11, 11);
+        let visible = room.field_of_view(Coord::new(5, 5), 3, algorithm);
+        for (row, col) in visible.iter_ones() {
+            assert!(row.abs_diff(5).pow(2) + col.abs_diff(5).pow(2) <= 9);
+        }
+        // 12 cells in straight lines, 8 on the diagonals, 8 a knight's move away, and
+        // the origin
+        assert_eq!(visible.count_ones(), 29);
+    }
+
+    #[rstest]
+    fn wall_blocks_sight(
+        #[values(FovAlgorithm::SymmetricShadowcasting, FovAlgorithm::RayCasting)]
+        algorithm: FovAlgorithm,
+    ) {
+        let board = picture(
+            "
+            .....#....
+            .....#....
+            .....#....
+            .....#....
+            .....#....
+        ",
+        );
+        let visible = board.field_of_view(Coord::new(2, 1), 20, algorithm);
+        // Everything up to and including the wall, and nothing behind it
+        for row in 0..5 {
+            for col in 0..10 {
+                assert_eq!(visible.get(row, col), col <= 5, "({row}, {col})");
+            }
+        }
+    }
+
+    #[test]
+    fn pillar_casts_shadow() {
+        let board = picture(
+            "
+            .......
+            .......
+            ...#...
+            .......
+            .......
+        ",
+        );
+        let visible =
+            board.field_of_view(Coord::new(2, 0), 10, FovAlgorithm::SymmetricShadowcasting);
+        assert!(visible.get(2, 3));
+        assert!(!visible.get(2, 4));
+        assert!(!visible.get(2, 6));
+        assert!(visible.get(0, 6));
+        assert!(visible.get(4, 6));
+    }
+
+    #[test]
+    fn shadowcasting_is_symmetric() {
+        let mut board = BitBoardDyn::new(15, 70);
+        for idx in 0..15 * 70 {
+            board
+                .board_mut()
+                .set(idx, idx.wrapping_mul(2_654_435_761) % 100 < 20);
+        }
+        let floors: Vec<Coord> = board.iter_zeros().map(Coord::from).step_by(17).collect();
+        let views: Vec<_> = floors
+            .iter()
+            .map(|&origin| board.field_of_view(origin, 30, FovAlgorithm::SymmetricShadowcasting))
+            .collect();
+        for (a, view_a) in floors.iter().zip(&views) {
+            for (b, view_b) in floors.iter().zip(&views) {
+                assert_eq!(
+                    view_a.get(b.row, b.col),
+                    view_b.get(a.row, a.col),
+                    "{a} and {b}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sight_stops_at_edges() {
+        let mut board = BitBoardStatic::<1>::new(4, 6).with_boundary(Boundary::Wrap);
+        board.set_col(1, true);
+        for algorithm in [
+            FovAlgorithm::SymmetricShadowcasting,
+            FovAlgorithm::RayCasting,
+        ] {
+            let visible = board.field_of_view(Coord::new(0, 0), 10, algorithm);
+            // Nothing is seen past the wall, or around the edge from the left
+            assert!(
+                visible.iter_ones().all(|(_, col)| col <= 1),
+                "{algorithm:?}"
+            );
+            assert_eq!(visible.get_col(0).filter(|&seen| seen).count(), 4);
+        }
+        let visible =
+            board.field_of_view(Coord::new(0, 0), 10, FovAlgorithm::SymmetricShadowcasting);
+        assert_eq!(visible.count_ones(), 8);
+        assert_eq!(
+            board
+                .field_of_view(Coord::new(3, 5), 0, FovAlgorithm::SymmetricShadowcasting)
+                .iter_ones()
+                .collect::<Vec<_>>(),
+            vec![(3, 5)]
+        );
+    }
+
+    #[test]
+    fn line_of_sight_queries() {
+        let board = picture(
+            "
+            ......
+            ..#...
+            ......
+        ",
+        );
+        assert!(board.has_line_of_sight(Coord::new(0, 0), Coord::new(0, 5)));
+        assert!(!board.has_line_of_sight(Coord::new(1, 0), Coord::new(1, 5)));
+        assert!(!board.has_line_of_sight(Coord::new(0, 0), Coord::new(2, 4)));
+        // The ends may be opaque
+        assert!(board.has_line_of_sight(Coord::new(1, 2), Coord::new(1, 5)));
+        assert!(board.has_line_of_sight(Coord::new(1, 0), Coord::new(1, 2)));
+    }
+
+    #[test]
+    fn unbounded_radius_sees_whole_room() {
+        let room = BitBoardDyn::new(5, 9);
+        for algorithm in [
+            FovAlgorithm::SymmetricShadowcasting,
+            FovAlgorithm::RayCasting,
+        ] {
+            let visible = room.field_of_view(Coord::new(2, 4), usize::MAX, algorithm);
+            assert_eq!(visible.count_ones(), 45, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "row cannot be greater than n_rows")]
+    fn ray_casting_out_of_bounds_panics() {
+        let board = BitBoardDyn::new(3, 3);
+        let _ = board.field_of_view(Coord::new(7, 0), 2, FovAlgorithm::RayCasting);
+    }
+
+    #[test]
+    #[should_panic(expected = "col cannot be greater than n_cols")]
+    fn line_of_sight_out_of_bounds_panics() {
+        let board = BitBoardDyn::new(3, 3);
+        let _ = board.has_line_of_sight(Coord::new(0, 0), Coord::new(0, 3));
+    }
+}
@@ -4,14 +4,125 @@ use std::fmt;
 pub mod bitboard;
 pub mod bitboarddyn;
 pub mod bitboardstatic;
+mod components;
+pub mod coord;
+pub mod distance;
+mod draw;
+pub mod fov;
+pub mod life;
+pub mod morphology;
+mod ops;
+pub mod parse;
+pub mod path;
+pub mod symmetry;
 
-#[derive(Debug)]
-pub struct DimensionMismatch;
+/// The ways in which building or combining boards can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitBoardError {
+    /// Two boards that must be the same size are not. Dimensions are
+    /// `(n_rows, n_cols)`.
+    DimensionMismatch {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
 
-impl fmt::Display for DimensionMismatch {
+    /// A board must have at least 1 row and 1 column.
+    ZeroSized { n_rows: usize, n_cols: usize },
+
+    /// The raw bits given for a board are not `n_rows * n_cols` long.
+    LengthMismatch { expected: usize, actual: usize },
+
+    /// The board needs more bits than the static storage can hold.
+    ExceedsCapacity { required: usize, available: usize },
+
+    /// A cell coordinate fell outside of the board.
+    OutOfBounds {
+        row: usize,
+        col: usize,
+        n_rows: usize,
+        n_cols: usize,
+    },
+
+    /// A row index fell outside of the board.
+    RowOutOfBounds { row: usize, n_rows: usize },
+
+    /// A column index fell outside of the board.
+    ColOutOfBounds { col: usize, n_cols: usize },
+
+    /// A linear index fell outside of the board.
+    IndexOutOfBounds { index: usize, len: usize },
+
+    /// A `height` x `width` region with its top left cell at (`row`, `col`) does not
+    /// fit on the board.
+    RegionOutOfBounds {
+        row: usize,
+        col: usize,
+        height: usize,
+        width: usize,
+        n_rows: usize,
+        n_cols: usize,
+    },
+}
+
+impl fmt::Display for BitBoardError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Dimensions do not match.")
+        match self {
+            BitBoardError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "Dimensions do not match: expected {}x{}, found {}x{}.",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            BitBoardError::ZeroSized { n_rows, n_cols } => write!(
+                f,
+                "Board must have at least 1 row and 1 column, found {n_rows}x{n_cols}."
+            ),
+            BitBoardError::LengthMismatch { expected, actual } => write!(
+                f,
+                "The board needs exactly {expected} bits, found {actual}."
+            ),
+            BitBoardError::ExceedsCapacity {
+                required,
+                available,
+            } => write!(
+                f,
+                "The number of bits required by the board ({required}) exceeds the allocated storage ({available} bits)."
+            ),
+            BitBoardError::OutOfBounds {
+                row,
+                col,
+                n_rows,
+                n_cols,
+            } => write!(
+                f,
+                "Cell ({row}, {col}) is out of bounds for a {n_rows}x{n_cols} board."
+            ),
+            BitBoardError::RowOutOfBounds { row, n_rows } => {
+                write!(
+                    f,
+                    "Row {row} is out of bounds for a board with {n_rows} rows."
+                )
+            }
+            BitBoardError::ColOutOfBounds { col, n_cols } => write!(
+                f,
+                "Column {col} is out of bounds for a board with {n_cols} columns."
+            ),
+            BitBoardError::IndexOutOfBounds { index, len } => write!(
+                f,
+                "Index {index} is out of bounds for a board with {len} cells."
+            ),
+            BitBoardError::RegionOutOfBounds {
+                row,
+                col,
+                height,
+                width,
+                n_rows,
+                n_cols,
+            } => write!(
+                f,
+                "A {height}x{width} region at ({row}, {col}) does not fit on a {n_rows}x{n_cols} board."
+            ),
+        }
     }
 }
 
-impl Error for DimensionMismatch {}
+impl Error for BitBoardError {}
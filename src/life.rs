@@ -0,0 +1,268 @@
+//! Life-like cellular automaton rules, written in the standard `B3/S23` notation.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A Life-like rule: which neighbor counts cause a dead cell to be born, and which let
+/// a live cell survive. Neighbors are the 8 surrounding cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// Bit `k` is set if a dead cell with `k` live neighbors becomes alive.
+    birth: u16,
+
+    /// Bit `k` is set if a live cell with `k` live neighbors stays alive.
+    survival: u16,
+}
+
+impl Rule {
+    /// Create a rule from the neighbor counts that cause birth and survival.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any count is greater than 8.
+    #[must_use]
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let mask = |counts: &[usize]| {
+            counts.iter().fold(0u16, |acc, &k| {
+                assert!(k <= 8, "neighbor count {k} cannot be greater than 8");
+                acc | (1 << k)
+            })
+        };
+        Rule {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Conway's Game of Life, `B3/S23`.
+    #[must_use]
+    pub fn conway() -> Self {
+        Rule::new(&[3], &[2, 3])
+    }
+
+    /// Returns true if a dead cell with `count` live neighbors becomes alive.
+    #[must_use]
+    pub fn births_on(&self, count: usize) -> bool {
+        count <= 8 && self.birth & (1 << count) != 0
+    }
+
+    /// Returns true if a live cell with `count` live neighbors stays alive.
+    #[must_use]
+    pub fn survives_on(&self, count: usize) -> bool {
+        count <= 8 && self.survival & (1 << count) != 0
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for count in (0..=8).filter(|&k| self.births_on(k)) {
+            write!(f, "{count}")?;
+        }
+        write!(f, "/S")?;
+        for count in (0..=8).filter(|&k| self.survives_on(k)) {
+            write!(f, "{count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = InvalidRule;
+
+    /// Parses a rulestring such as `B3/S23`. The `B` and `S` parts may come in either
+    /// order and in either case. The older `S/B` form without letters, such as `23/3`,
+    /// is also accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || InvalidRule {
+            rule: s.to_string(),
+        };
+        let parse_counts = |digits: &str| -> Result<Vec<usize>, InvalidRule> {
+            digits
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(k) if k <= 8 => Ok(k as usize),
+                    _ => Err(err()),
+                })
+                .collect()
+        };
+
+        let (first, second) = s.trim().split_once('/').ok_or_else(err)?;
+        let mut birth = None;
+        let mut survival = None;
+        for part in [first, second] {
+            let mut chars = part.chars();
+            match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') if birth.is_none() => birth = Some(parse_counts(chars.as_str())?),
+                Some('S') if survival.is_none() => {
+                    survival = Some(parse_counts(chars.as_str())?);
+                }
+                _ => break,
+            }
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Rule::new(&birth, &survival)),
+            // Legacy notation lists the survival counts first
+            (None, None) => Ok(Rule::new(&parse_counts(second)?, &parse_counts(first)?)),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// The rulestring could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRule {
+    rule: String,
+}
+
+impl fmt::Display for InvalidRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid rulestring {:?}, expected a rule like \"B3/S23\".",
+            self.rule
+        )
+    }
+}
+
+impl Error for InvalidRule {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bitboard::{BitBoard, Boundary},
+        bitboarddyn::BitBoardDyn,
+        bitboardstatic::BitBoardStatic,
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("B3/S23", Rule::new(&[3], &[2, 3]))]
+    #[case("b3/s23", Rule::new(&[3], &[2, 3]))]
+    #[case("S23/B3", Rule::new(&[3], &[2, 3]))]
+    #[case("23/3", Rule::new(&[3], &[2, 3]))]
+    #[case("B36/S23", Rule::new(&[3, 6], &[2, 3]))]
+    #[case("B0123478/S01234678", Rule::new(&[0, 1, 2, 3, 4, 7, 8], &[0, 1, 2, 3, 4, 6, 7, 8]))]
+    #[case("B2/S", Rule::new(&[2], &[]))]
+    fn parse_rule(#[case] text: &str, #[case] expected: Rule) {
+        assert_eq!(text.parse::<Rule>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("B3S23")]
+    #[case("B9/S23")]
+    #[case("B3/S2x")]
+    #[case("B3/B23")]
+    #[case("B3/23")]
+    fn parse_invalid_rule(#[case] text: &str) {
+        assert!(text.parse::<Rule>().is_err());
+    }
+
+    #[rstest]
+    #[case("B3/S23")]
+    #[case("B36/S23")]
+    #[case("B0/S8")]
+    #[case("B/S")]
+    fn rule_display_round_trips(#[case] text: &str) {
+        let rule: Rule = text.parse().unwrap();
+        assert_eq!(rule.to_string(), text);
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let mut bb = BitBoardStatic::<1>::new(5, 5);
+        bb.set(2, 1, true);
+        bb.set(2, 2, true);
+        bb.set(2, 3, true);
+
+        let next = bb.step(&Rule::conway());
+        let mut expected = BitBoardStatic::<1>::new(5, 5);
+        expected.set_col(2, true);
+        expected.set(0, 2, false);
+        expected.set(4, 2, false);
+        assert_eq!(next, expected);
+        assert_eq!(next.step(&Rule::conway()), bb);
+    }
+
+    #[test]
+    fn block_is_still_life() {
+        let mut bb = BitBoardDyn::new(4, 4);
+        for (r, c) in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+            bb.set(r, c, true);
+        }
+        assert_eq!(bb.step(&Rule::conway()), bb);
+    }
+
+    #[test]
+    fn glider_wraps_around_torus() {
+        let mut bb = BitBoardDyn::new(8, 72).with_boundary(Boundary::Wrap);
+        for (r, c) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            bb.set(r, c, true);
+        }
+        // A glider moves one cell diagonally every 4 generations, so it needs
+        // lcm(8, 72) * 4 generations to come back to where it started.
+        let mut next = bb.clone();
+        for _ in 0..72 * 4 {
+            next = next.step(&Rule::conway());
+        }
+        assert_eq!(next, bb);
+    }
+
+    #[test]
+    fn glider_dies_against_hard_edge() {
+        let mut bb = BitBoardDyn::new(6, 6);
+        for (r, c) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            bb.set(r, c, true);
+        }
+        for _ in 0..40 {
+            bb = bb.step(&Rule::conway());
+        }
+        // It ends up as a block in the corner
+        let mut block = BitBoardDyn::new(6, 6);
+        for (r, c) in [(4, 4), (4, 5), (5, 4), (5, 5)] {
+            block.set(r, c, true);
+        }
+        assert_eq!(bb, block);
+    }
+
+    #[test]
+    fn b0_rule_births_isolated_cells() {
+        let mut bb = BitBoardDyn::new(3, 5);
+        bb.set(1, 0, true);
+
+        let next = bb.step(&"B0/S".parse().unwrap());
+        // Only cells with no live neighbors are born; the live cell dies
+        let mut expected = BitBoardDyn::new(3, 5);
+        expected.set_col(2, true);
+        expected.set_col(3, true);
+        expected.set_col(4, true);
+        assert_eq!(next, expected);
+    }
+
+    #[test]
+    fn step_matches_per_cell_rules() {
+        let rule: Rule = "B1357/S02468".parse().unwrap();
+        let mut bb = BitBoardDyn::new(7, 9);
+        for idx in (0..63).filter(|i| i % 4 == 1 || i % 5 == 0) {
+            bb.board_mut().set(idx, true);
+        }
+        let next = bb.step(&rule);
+        for row in 0..7 {
+            for col in 0..9 {
+                let mut probe = BitBoardDyn::new(7, 9);
+                probe.set_all_neighbors(row, col, true);
+                let count = probe.and(&bb).unwrap().board().count_ones();
+                let expected = if bb.get(row, col) {
+                    rule.survives_on(count)
+                } else {
+                    rule.births_on(count)
+                };
+                assert_eq!(next.get(row, col), expected, "({row}, {col})");
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Structuring elements for the binary morphology methods on [`BitBoard`], such as
+//! [`BitBoard::dilate`] and [`BitBoard::erode`].
+
+use crate::bitboard::{BitBoard, Connectivity};
+
+/// A shape that is stamped onto every cell of a board by dilation, or that must fit
+/// around a cell for it to survive erosion. It is stored as the `(d_row, d_col)`
+/// offsets of its cells from its origin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StructuringElement {
+    offsets: Vec<(isize, isize)>,
+}
+
+impl StructuringElement {
+    /// Create an element from the set cells of `board`, with its origin at
+    /// [origin_row, origin_col]. The origin does not have to be set, or even be on the
+    /// board.
+    #[must_use]
+    pub fn new(board: &impl BitBoard, origin_row: isize, origin_col: isize) -> Self {
+        let offsets = board
+            .iter_ones()
+            .map(|(row, col)| (row as isize - origin_row, col as isize - origin_col))
+            .collect();
+        StructuringElement { offsets }
+    }
+
+    /// Create an element from the set cells of `board`, with its origin at the center
+    /// cell, or just above and left of the center for even dimensions.
+    #[must_use]
+    pub fn centered(board: &impl BitBoard) -> Self {
+        let origin_row = (board.n_rows() - 1) / 2;
+        let origin_col = (board.n_cols() - 1) / 2;
+        Self::new(board, origin_row as isize, origin_col as isize)
+    }
+
+    /// Returns the `(d_row, d_col)` offset of each cell from the origin.
+    #[must_use]
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    /// Returns the element mirrored through its origin.
+    #[must_use]
+    pub fn reflect(&self) -> Self {
+        let offsets = self
+            .offsets
+            .iter()
+            .map(|&(d_row, d_col)| (-d_row, -d_col))
+            .collect();
+        StructuringElement { offsets }
+    }
+}
+
+impl From<Connectivity> for StructuringElement {
+    /// The origin and its neighbors: a plus sign for [`Connectivity::Four`] and a 3x3
+    /// square for [`Connectivity::Eight`].
+    fn from(connectivity: Connectivity) -> Self {
+        let mut offsets = vec![(0, 0)];
+        offsets.extend_from_slice(connectivity.offsets());
+        StructuringElement { offsets }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboarddyn::BitBoardDyn;
+
+    #[test]
+    fn centered_offsets() {
+        let board = BitBoardDyn::from_coords(3, 3, [(0, 1), (1, 1), (2, 2)]).unwrap();
+        let element = StructuringElement::centered(&board);
+        assert_eq!(element.offsets(), &[(-1, 0), (0, 0), (1, 1)]);
+        assert_eq!(element.reflect().offsets(), &[(1, 0), (0, 0), (-1, -1)]);
+
+        // An even side puts the origin before the middle
+        let board = BitBoardDyn::from_coords(2, 2, [(0, 0), (1, 1)]).unwrap();
+        let element = StructuringElement::centered(&board);
+        assert_eq!(element.offsets(), &[(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn connectivity_elements() {
+        let four = StructuringElement::from(Connectivity::Four);
+        let eight = StructuringElement::from(Connectivity::Eight);
+        assert_eq!(four.offsets().len(), 5);
+        assert_eq!(eight.offsets().len(), 9);
+
+        let mut square = BitBoardDyn::new(3, 3);
+        square.fill(true);
+        let mut from_square = StructuringElement::centered(&square).offsets().to_vec();
+        let mut from_eight = eight.offsets().to_vec();
+        from_square.sort_unstable();
+        from_eight.sort_unstable();
+        assert_eq!(from_square, from_eight);
+    }
+}
@@ -0,0 +1,225 @@
+//! Operator overloads (`&`, `|`, `^`, `!` and their assigning forms, and indexing by
+//! cell) for the board types.
+//!
+//! Unlike the `Result`-returning methods on [`BitBoard`], these panic if the two
+//! boards have different dimensions, or if an index is out of bounds.
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not};
+
+use crate::bitboard::BitBoard;
+use crate::bitboarddyn::BitBoardDyn;
+use crate::bitboardstatic::BitBoardStatic;
+use crate::coord::Coord;
+
+/// Implements one binary operator, plus its assigning form, for owned and borrowed
+/// operands, in terms of the matching in-place `BitBoard` method.
+macro_rules! impl_binary_op {
+    ([$($gen:tt)*] $ty:ty, $op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $method:ident) => {
+        impl<$($gen)*> $assign<&$ty> for $ty {
+            fn $assign_fn(&mut self, rhs: &$ty) {
+                if let Err(err) = self.$method(rhs) {
+                    panic!("{err}");
+                }
+            }
+        }
+
+        impl<$($gen)*> $assign<$ty> for $ty {
+            fn $assign_fn(&mut self, rhs: $ty) {
+                self.$assign_fn(&rhs);
+            }
+        }
+
+        impl<$($gen)*> $op<&$ty> for $ty {
+            type Output = $ty;
+
+            fn $op_fn(mut self, rhs: &$ty) -> $ty {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+
+        impl<$($gen)*> $op<$ty> for $ty {
+            type Output = $ty;
+
+            fn $op_fn(mut self, rhs: $ty) -> $ty {
+                self.$assign_fn(&rhs);
+                self
+            }
+        }
+
+        impl<$($gen)*> $op<&$ty> for &$ty {
+            type Output = $ty;
+
+            fn $op_fn(self, rhs: &$ty) -> $ty {
+                self.clone().$op_fn(rhs)
+            }
+        }
+
+        impl<$($gen)*> $op<$ty> for &$ty {
+            type Output = $ty;
+
+            fn $op_fn(self, rhs: $ty) -> $ty {
+                self.clone().$op_fn(&rhs)
+            }
+        }
+    };
+}
+
+/// Implements all of the bitwise operators for a board type.
+macro_rules! impl_bitwise_ops {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl_binary_op!([$($gen)*] $ty, BitAnd, bitand, BitAndAssign, bitand_assign, and_assign);
+        impl_binary_op!([$($gen)*] $ty, BitOr, bitor, BitOrAssign, bitor_assign, or_assign);
+        impl_binary_op!([$($gen)*] $ty, BitXor, bitxor, BitXorAssign, bitxor_assign, xor_assign);
+
+        impl<$($gen)*> Not for $ty {
+            type Output = $ty;
+
+            fn not(mut self) -> $ty {
+                self.not_assign();
+                self
+            }
+        }
+
+        impl<$($gen)*> Not for &$ty {
+            type Output = $ty;
+
+            fn not(self) -> $ty {
+                BitBoard::not(self)
+            }
+        }
+    };
+}
+
+/// Implements indexing a board type by `(row, col)` and by [`Coord`].
+macro_rules! impl_index {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<$($gen)*> Index<(usize, usize)> for $ty {
+            type Output = bool;
+
+            fn index(&self, (row, col): (usize, usize)) -> &bool {
+                &self.board()[self.index_of(row, col)]
+            }
+        }
+
+        impl<$($gen)*> Index<Coord> for $ty {
+            type Output = bool;
+
+            fn index(&self, coord: Coord) -> &bool {
+                &self[(coord.row, coord.col)]
+            }
+        }
+    };
+}
+
+impl_bitwise_ops!([] BitBoardDyn);
+impl_bitwise_ops!([const W: usize] BitBoardStatic<W>);
+impl_index!([] BitBoardDyn);
+impl_index!([const W: usize] BitBoardStatic<W>);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bitboard::BitBoard, bitboarddyn::BitBoardDyn, bitboardstatic::BitBoardStatic, coord::Coord,
+    };
+
+    fn dyn_boards() -> (BitBoardDyn, BitBoardDyn) {
+        let mut a = BitBoardDyn::new(3, 4);
+        a.set_row(0, true);
+        a.set(2, 3, true);
+
+        let mut b = BitBoardDyn::new(3, 4);
+        b.set_col(0, true);
+        b.set(2, 3, true);
+        (a, b)
+    }
+
+    #[test]
+    fn dyn_operators_match_methods() {
+        let (a, b) = dyn_boards();
+        assert_eq!(&a & &b, a.and(&b).unwrap());
+        assert_eq!(&a | &b, a.or(&b).unwrap());
+        assert_eq!(&a ^ &b, a.xor(&b).unwrap());
+        assert_eq!(!&a, a.not());
+
+        assert_eq!(a.clone() & b.clone(), a.and(&b).unwrap());
+        assert_eq!(a.clone() | &b, a.or(&b).unwrap());
+        assert_eq!(&a ^ b.clone(), a.xor(&b).unwrap());
+        assert_eq!(!a.clone(), a.not());
+    }
+
+    #[test]
+    fn dyn_assign_operators_match_methods() {
+        let (a, b) = dyn_boards();
+
+        let mut c = a.clone();
+        c &= &b;
+        assert_eq!(c, a.and(&b).unwrap());
+
+        let mut c = a.clone();
+        c |= b.clone();
+        assert_eq!(c, a.or(&b).unwrap());
+
+        let mut c = a.clone();
+        c ^= &b;
+        assert_eq!(c, a.xor(&b).unwrap());
+    }
+
+    #[test]
+    fn static_operators_match_methods() {
+        let mut a = BitBoardStatic::<1>::new(3, 3);
+        a.set_row(1, true);
+        let mut b = BitBoardStatic::<1>::new(3, 3);
+        b.set_col(1, true);
+
+        assert_eq!(a & b, a.and(&b).unwrap());
+        assert_eq!(a | b, a.or(&b).unwrap());
+        assert_eq!(a ^ b, a.xor(&b).unwrap());
+        assert_eq!(!a, a.not());
+        assert_eq!((a | b) & !(a & b), a ^ b);
+
+        let mut c = a;
+        c ^= b;
+        c |= &a;
+        c &= &b;
+        // (a ^ b) | a == a | b, and (a | b) & b == b
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Dimensions do not match")]
+    fn operator_dimension_mismatch_panics() {
+        let a = BitBoardDyn::new(2, 3);
+        let b = BitBoardDyn::new(3, 2);
+        let _ = a & b;
+    }
+
+    #[test]
+    #[should_panic(expected = "Dimensions do not match")]
+    fn assign_operator_dimension_mismatch_panics() {
+        let mut a = BitBoardStatic::<1>::new(2, 3);
+        let b = BitBoardStatic::<1>::new(3, 2);
+        a |= b;
+    }
+
+    #[test]
+    fn index_by_tuple_and_coord() {
+        let mut dyn_board = BitBoardDyn::new(3, 70);
+        dyn_board.set(2, 69, true);
+        assert!(dyn_board[(2, 69)]);
+        assert!(!dyn_board[(0, 0)]);
+        assert!(dyn_board[Coord::new(2, 69)]);
+
+        let mut static_board = BitBoardStatic::<1>::new(2, 2);
+        static_board.set(1, 0, true);
+        assert!(static_board[(1, 0)]);
+        assert!(!static_board[Coord::new(0, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "col cannot be greater than n_cols")]
+    fn index_out_of_bounds_panics() {
+        let bb = BitBoardDyn::new(2, 2);
+        let _ = bb[(0, 2)];
+    }
+}
@@ -0,0 +1,254 @@
+//! Parsing boards back out of text, such as the ASCII art written by `Display`.
+
+use std::error::Error;
+use std::fmt;
+
+use bitvec::prelude::*;
+
+use crate::BitBoardError;
+
+/// How a board is laid out as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// The character for a set cell.
+    pub on: char,
+
+    /// The character for an unset cell.
+    pub off: char,
+
+    /// Whether the text has the header row of column digits and the row numbers
+    /// written by `Display`.
+    pub indices: bool,
+}
+
+impl Default for ParseOptions {
+    /// The format written by `Display`: `X` and `.` cells with indices.
+    fn default() -> Self {
+        ParseOptions {
+            on: 'X',
+            off: '.',
+            indices: true,
+        }
+    }
+}
+
+/// The text could not be parsed as a board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBoardError {
+    /// There were no rows of cells.
+    Empty,
+
+    /// The header row did not count the columns as `0123456789012...`.
+    BadHeader,
+
+    /// A row was missing its row number, or had the wrong one.
+    BadRowLabel { line: usize },
+
+    /// A row had a character that was neither the on nor the off character.
+    UnexpectedChar { line: usize, found: char },
+
+    /// A row had a different number of cells than the first row.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    /// The text described a board that cannot be built, such as one too big for its
+    /// static storage.
+    Board(BitBoardError),
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoardError::Empty => write!(f, "The board has no rows."),
+            ParseBoardError::BadHeader => {
+                write!(f, "The header row does not match the column indices.")
+            }
+            ParseBoardError::BadRowLabel { line } => {
+                write!(f, "Line {line} does not start with its row number.")
+            }
+            ParseBoardError::UnexpectedChar { line, found } => {
+                write!(f, "Line {line} has an unexpected character {found:?}.")
+            }
+            ParseBoardError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line {line} has {found} cells, but the board has {expected} columns."
+            ),
+            ParseBoardError::Board(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ParseBoardError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseBoardError::Board(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<BitBoardError> for ParseBoardError {
+    fn from(err: BitBoardError) -> Self {
+        ParseBoardError::Board(err)
+    }
+}
+
+/// Parses `text` into its number of rows, number of columns, and row-major cells.
+/// Blank lines and whitespace around each line are ignored, so the on and off
+/// characters should not be whitespace.
+pub(crate) fn parse_cells(
+    text: &str,
+    options: &ParseOptions,
+) -> Result<(usize, usize, BitVec), ParseBoardError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let header = if options.indices {
+        let (_, header) = lines.next().ok_or(ParseBoardError::Empty)?;
+        let counts_columns = header
+            .chars()
+            .enumerate()
+            .all(|(col, c)| c.to_digit(10) == Some((col % 10) as u32));
+        if !counts_columns {
+            return Err(ParseBoardError::BadHeader);
+        }
+        Some(header.len())
+    } else {
+        None
+    };
+
+    let mut bits = BitVec::new();
+    let mut n_rows = 0;
+    let mut n_cols = None;
+    for (line_no, line) in lines {
+        let cells = if options.indices {
+            match line.split_once(' ') {
+                Some((label, cells)) if label.parse() == Ok(n_rows) => cells.trim_start(),
+                _ => return Err(ParseBoardError::BadRowLabel { line: line_no }),
+            }
+        } else {
+            line
+        };
+
+        let mut found = 0;
+        for c in cells.chars() {
+            let bit = match c {
+                c if c == options.on => true,
+                c if c == options.off => false,
+                found => {
+                    return Err(ParseBoardError::UnexpectedChar {
+                        line: line_no,
+                        found,
+                    });
+                }
+            };
+            bits.push(bit);
+            found += 1;
+        }
+
+        let expected = *n_cols.get_or_insert(header.unwrap_or(found));
+        if found != expected {
+            return Err(ParseBoardError::RaggedRow {
+                line: line_no,
+                expected,
+                found,
+            });
+        }
+        n_rows += 1;
+    }
+
+    match n_cols {
+        Some(n_cols) if n_cols > 0 => Ok((n_rows, n_cols, bits)),
+        _ => Err(ParseBoardError::Empty),
+    }
+}
+
+/// Parses a picture for a test, where `#` is set and `.` is unset, with no indices.
+///
+/// # Panics
+///
+/// Panics if the picture is not a valid board.
+#[cfg(test)]
+pub(crate) fn picture(text: &str) -> crate::bitboarddyn::BitBoardDyn {
+    let options = ParseOptions {
+        on: '#',
+        off: '.',
+        indices: false,
+    };
+    crate::bitboarddyn::BitBoardDyn::parse_with(text, &options).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitboard::BitBoard, bitboarddyn::BitBoardDyn, bitboardstatic::BitBoardStatic};
+    use rstest::rstest;
+
+    #[test]
+    fn parse_display_format() {
+        let text = "   0123\n 0 X..X\n 1 .X..\n";
+        let (n_rows, n_cols, bits) = parse_cells(text, &ParseOptions::default()).unwrap();
+        assert_eq!((n_rows, n_cols), (2, 4));
+        assert_eq!(bits, bitvec![1, 0, 0, 1, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn parse_without_indices() {
+        let options = ParseOptions {
+            on: '#',
+            off: '_',
+            indices: false,
+        };
+        let text = "
+            #_#
+            ___
+        ";
+        let (n_rows, n_cols, bits) = parse_cells(text, &options).unwrap();
+        assert_eq!((n_rows, n_cols), (2, 3));
+        assert_eq!(bits, bitvec![1, 0, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn custom_chars_round_trip() {
+        let options = ParseOptions {
+            on: '#',
+            off: '-',
+            indices: false,
+        };
+        let text = "#-#\n-#-\n";
+        let expected = vec![(0, 0), (0, 2), (1, 1)];
+
+        let dyn_board = BitBoardDyn::parse_with(text, &options).unwrap();
+        assert_eq!(dyn_board.iter_ones().collect::<Vec<_>>(), expected);
+        assert_eq!(dyn_board.to_string().parse(), Ok(dyn_board));
+
+        let static_board = BitBoardStatic::<1>::parse_with(text, &options).unwrap();
+        assert_eq!(static_board.iter_ones().collect::<Vec<_>>(), expected);
+        assert_eq!(static_board.to_string().parse(), Ok(static_board));
+    }
+
+    #[rstest]
+    #[case("", ParseBoardError::Empty)]
+    #[case("   012\n", ParseBoardError::Empty)]
+    #[case("   013\n 0 ...\n", ParseBoardError::BadHeader)]
+    #[case("   012\n 1 ...\n", ParseBoardError::BadRowLabel { line: 2 })]
+    #[case("   012\n ...\n", ParseBoardError::BadRowLabel { line: 2 })]
+    #[case("   012\n 0 .o.\n", ParseBoardError::UnexpectedChar { line: 2, found: 'o' })]
+    #[case("   012\n 0 ...\n 1 ....\n", ParseBoardError::RaggedRow { line: 3, expected: 3, found: 4 })]
+    fn parse_errors(#[case] text: &str, #[case] expected: ParseBoardError) {
+        assert_eq!(
+            parse_cells(text, &ParseOptions::default()).unwrap_err(),
+            expected
+        );
+    }
+}
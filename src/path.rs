@@ -0,0 +1,923 @@
+//! Shortest paths across a grid map.
+//!
+//! The two kinds of search read a board the opposite way round:
+//!
+//! - The breadth-first searches, [`BitBoard::shortest_path`] and
+//!   [`BitBoard::distance_map`], take a walkable mask: they walk across the set cells.
+//! - The weighted searches, [`BitBoard::astar`] and [`BitBoard::jump_point_search`],
+//!   take an obstacle mask: set cells are blocked, and they walk across the unset
+//!   cells.
+//!
+//! Use [`BitBoard::not`] to turn one kind of mask into the other.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::f64::consts::SQRT_2;
+
+use bitvec::slice::BitSlice;
+
+use crate::bitboard::{BitBoard, Boundary, Connectivity, grown};
+use crate::coord::Coord;
+
+/// An estimate of the cost of the path between two cells, used to guide
+/// [`BitBoard::astar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Heuristic {
+    /// The number of rows plus the number of columns apart. This is only a lower bound,
+    /// and so only finds shortest paths, when diagonal moves are not allowed.
+    Manhattan,
+
+    /// The cost of walking diagonally as far as possible and then straight, with
+    /// diagonal steps costing the square root of 2. This is the exact cost on an open
+    /// board when diagonal moves are allowed.
+    Octile,
+
+    /// The larger of the number of rows and the number of columns apart.
+    Chebyshev,
+}
+
+impl Heuristic {
+    /// Returns the estimated cost of moving `d_row` rows and `d_col` columns.
+    #[must_use]
+    pub fn estimate(self, d_row: usize, d_col: usize) -> f64 {
+        let (near, far) = (d_row.min(d_col) as f64, d_row.max(d_col) as f64);
+        match self {
+            Heuristic::Manhattan => near + far,
+            Heuristic::Octile => far + (SQRT_2 - 1.0) * near,
+            Heuristic::Chebyshev => far,
+        }
+    }
+}
+
+/// When a path may step diagonally, based on the two cells it would cut the corner of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagonalMovement {
+    /// Only step up, down, left, and right.
+    Never,
+
+    /// Step diagonally even between two blocked cells.
+    Always,
+
+    /// Step diagonally if at least one of the two cells beside the step is open, so
+    /// the path never squeezes between two blocked cells.
+    IfAtMostOneBlocked,
+
+    /// Step diagonally only if both cells beside the step are open, so the path never
+    /// cuts the corner of a blocked cell.
+    IfNoneBlocked,
+}
+
+/// A path found by a weighted search such as [`BitBoard::astar`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    /// The cells of the path, including the start and the goal.
+    pub cells: Vec<Coord>,
+
+    /// The total cost of the steps: 1 for each straight step and the square root of 2
+    /// for each diagonal step.
+    pub cost: f64,
+}
+
+/// Returns each unset cell one step from `cell` on an obstacle mask, with the cost of
+/// the step.
+pub(crate) fn moves<B: BitBoard>(
+    board: &B,
+    cell: Coord,
+    diagonal: DiagonalMovement,
+) -> impl Iterator<Item = (Coord, f64)> {
+    let step = move |d_row: isize, d_col: isize| {
+        board
+            .neighbor_of(cell.row, cell.col, d_row, d_col)
+            .map(Coord::from)
+            .filter(|next| !board.get(next.row, next.col))
+    };
+    let straight = Connectivity::Four
+        .offsets()
+        .iter()
+        .filter_map(move |&(d_row, d_col)| step(d_row, d_col).map(|next| (next, 1.0)));
+    let diagonals = Connectivity::Eight.offsets()[4..]
+        .iter()
+        .filter_map(move |&(d_row, d_col)| {
+            let next = step(d_row, d_col)?;
+            let open_sides =
+                usize::from(step(d_row, 0).is_some()) + usize::from(step(0, d_col).is_some());
+            let allowed = match diagonal {
+                DiagonalMovement::Never => false,
+                DiagonalMovement::Always => true,
+                DiagonalMovement::IfAtMostOneBlocked => open_sides >= 1,
+                DiagonalMovement::IfNoneBlocked => open_sides == 2,
+            };
+            allowed.then_some((next, SQRT_2))
+        });
+    straight.chain(diagonals)
+}
+
+/// Returns the number of rows and columns between two cells, going the short way
+/// around a wrapping board.
+pub(crate) fn separation<B: BitBoard>(board: &B, a: Coord, b: Coord) -> (usize, usize) {
+    let d_row = a.row.abs_diff(b.row);
+    let d_col = a.col.abs_diff(b.col);
+    match board.boundary() {
+        Boundary::Hard => (d_row, d_col),
+        Boundary::Wrap => (
+            d_row.min(board.n_rows() - d_row),
+            d_col.min(board.n_cols() - d_col),
+        ),
+    }
+}
+
+/// An entry in the open set of a weighted search, ordered so that the smallest
+/// estimated total comes first, breaking ties towards the cell furthest along.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OpenCell {
+    pub(crate) estimate: f64,
+    pub(crate) cost: f64,
+    pub(crate) idx: usize,
+}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.estimate
+            .total_cmp(&other.estimate)
+            .then_with(|| other.cost.total_cmp(&self.cost))
+            .then_with(|| self.idx.cmp(&other.idx))
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OpenCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for OpenCell {}
+
+/// Follows `parents` back from `goal` to the cell that is its own parent.
+pub(crate) fn trace_back<B: BitBoard>(board: &B, parents: &[usize], goal: usize) -> Vec<Coord> {
+    let mut cells = vec![Coord::from(board.row_col_of(goal))];
+    let mut idx = goal;
+    while parents[idx] != idx {
+        idx = parents[idx];
+        cells.push(Coord::from(board.row_col_of(idx)));
+    }
+    cells.reverse();
+    cells
+}
+
+/// Runs A* from `start` to `goal` on an obstacle mask, expanding each cell to its
+/// unset neighbors.
+pub(crate) fn astar<B: BitBoard>(
+    board: &B,
+    start: Coord,
+    goal: Coord,
+    heuristic: Heuristic,
+    diagonal: DiagonalMovement,
+) -> Option<Path> {
+    let start_idx = board.index_of(start.row, start.col);
+    let goal_idx = board.index_of(goal.row, goal.col);
+    if board.board()[start_idx] || board.board()[goal_idx] {
+        return None;
+    }
+
+    let estimate = |cell: Coord| {
+        let (d_row, d_col) = separation(board, cell, goal);
+        heuristic.estimate(d_row, d_col)
+    };
+    let mut costs = vec![f64::INFINITY; board.n_rows() * board.n_cols()];
+    let mut parents = vec![usize::MAX; costs.len()];
+    costs[start_idx] = 0.0;
+    parents[start_idx] = start_idx;
+
+    let mut open = BinaryHeap::from([Reverse(OpenCell {
+        estimate: estimate(start),
+        cost: 0.0,
+        idx: start_idx,
+    })]);
+    while let Some(Reverse(OpenCell { cost, idx, .. })) = open.pop() {
+        if idx == goal_idx {
+            return Some(Path {
+                cells: trace_back(board, &parents, goal_idx),
+                cost,
+            });
+        }
+        // Skip cells that were reached more cheaply after this entry was added
+        if cost > costs[idx] {
+            continue;
+        }
+
+        let cell = Coord::from(board.row_col_of(idx));
+        for (next, step_cost) in moves(board, cell, diagonal) {
+            let next_idx = board.index_of(next.row, next.col);
+            let next_cost = cost + step_cost;
+            if next_cost < costs[next_idx] {
+                costs[next_idx] = next_cost;
+                parents[next_idx] = idx;
+                open.push(Reverse(OpenCell {
+                    estimate: next_cost + estimate(next),
+                    cost: next_cost,
+                    idx: next_idx,
+                }));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the number of steps from `start` to each cell, in row-major order, or `None`
+/// for cells that cannot be reached. The search stops early once `goal` is reached.
+///
+/// The search expands a whole frontier at a time: the cells reached in `d` steps are
+/// grown by one step and masked to the walkable cells not yet seen.
+pub(crate) fn bfs_distances<B: BitBoard>(
+    board: &B,
+    start: Coord,
+    connectivity: Connectivity,
+    goal: Option<Coord>,
+) -> Vec<Option<u32>> {
+    let mut distances = vec![None; board.n_rows() * board.n_cols()];
+    let start_idx = board.index_of(start.row, start.col);
+    if !board.board()[start_idx] {
+        return distances;
+    }
+    distances[start_idx] = Some(0);
+    let goal_idx = goal.map(|goal| board.index_of(goal.row, goal.col));
+
+    // Cells that are walkable and not yet reached
+    let mut unseen = board.clone();
+    unseen.board_mut().set(start_idx, false);
+    let mut frontier = board.empty_with_dims(board.n_rows(), board.n_cols());
+    frontier.board_mut().set(start_idx, true);
+
+    let mut distance = 0;
+    while goal_idx.is_none_or(|idx| distances[idx].is_none()) {
+        frontier = grown(&frontier, connectivity);
+        *frontier.board_mut() &= unseen.board();
+        if frontier.board().not_any() {
+            break;
+        }
+        distance += 1;
+        for idx in frontier.board().iter_ones() {
+            distances[idx] = Some(distance);
+        }
+        *unseen.board_mut() ^= frontier.board();
+    }
+    distances
+}
+
+/// Returns the cells of a shortest path from `start` to `goal`, including both ends, or
+/// `None` if `goal` cannot be reached.
+pub(crate) fn bfs_path<B: BitBoard>(
+    board: &B,
+    start: Coord,
+    goal: Coord,
+    connectivity: Connectivity,
+) -> Option<Vec<Coord>> {
+    let distances = bfs_distances(board, start, connectivity, Some(goal));
+    let distance_at = |coord: Coord| distances[board.index_of(coord.row, coord.col)];
+
+    // Walk back from the goal, always to a neighbor one step closer to the start
+    let mut path = vec![goal];
+    let mut current = goal;
+    let mut distance = distance_at(goal)?;
+    while distance > 0 {
+        distance -= 1;
+        current = connectivity
+            .offsets()
+            .iter()
+            .filter_map(|&(d_row, d_col)| board.neighbor_of(current.row, current.col, d_row, d_col))
+            .map(Coord::from)
+            .find(|&next| distance_at(next) == Some(distance))
+            .expect("every reached cell has a neighbor one step closer");
+        path.push(current);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// An obstacle mask prepared for Jump Point Search, kept both row-major and
+/// column-major so that jumps in any straight direction scan a contiguous run of bits.
+/// One grid can serve any number of searches on the same board.
+pub(crate) struct JumpGrid<'a, B> {
+    board: &'a B,
+    transposed: B,
+}
+
+impl<'a, B: BitBoard> JumpGrid<'a, B> {
+    /// Prepares `board`, an obstacle mask where set cells are blocked, for searching.
+    pub(crate) fn new(board: &'a B) -> Self {
+        JumpGrid {
+            board,
+            transposed: board.transpose(),
+        }
+    }
+
+    /// Runs Jump Point Search from `start` to `goal` on a hard board, moving diagonally
+    /// only when no corner is cut. A wrapping board falls back to [`astar`].
+    pub(crate) fn search(&self, start: Coord, goal: Coord) -> Option<Path> {
+        let board = self.board;
+        if board.boundary() == Boundary::Wrap {
+            return astar(
+                board,
+                start,
+                goal,
+                Heuristic::Octile,
+                DiagonalMovement::IfNoneBlocked,
+            );
+        }
+        let start_idx = board.index_of(start.row, start.col);
+        let goal_idx = board.index_of(goal.row, goal.col);
+        if board.board()[start_idx] || board.board()[goal_idx] {
+            return None;
+        }
+
+        let octile = |a: Coord, b: Coord| {
+            Heuristic::Octile.estimate(a.row.abs_diff(b.row), a.col.abs_diff(b.col))
+        };
+        let mut costs = vec![f64::INFINITY; board.n_rows() * board.n_cols()];
+        let mut parents = vec![usize::MAX; costs.len()];
+        costs[start_idx] = 0.0;
+        parents[start_idx] = start_idx;
+        let mut open = BinaryHeap::from([Reverse(OpenCell {
+            estimate: octile(start, goal),
+            cost: 0.0,
+            idx: start_idx,
+        })]);
+        while let Some(Reverse(OpenCell { cost, idx, .. })) = open.pop() {
+            if idx == goal_idx {
+                // Fill in the straight and diagonal runs between the jump points
+                let jump_points = trace_back(board, &parents, goal_idx);
+                let mut cells = vec![start];
+                for next in &jump_points[1..] {
+                    let mut cell = *cells.last().expect("there is always the start");
+                    while cell != *next {
+                        cell.row = cell
+                            .row
+                            .wrapping_add_signed((next.row as isize - cell.row as isize).signum());
+                        cell.col = cell
+                            .col
+                            .wrapping_add_signed((next.col as isize - cell.col as isize).signum());
+                        cells.push(cell);
+                    }
+                }
+                return Some(Path { cells, cost });
+            }
+            if cost > costs[idx] {
+                continue;
+            }
+
+            let cell = Coord::from(board.row_col_of(idx));
+            let parent = (parents[idx] != idx).then(|| Coord::from(board.row_col_of(parents[idx])));
+            for (d_row, d_col) in self.directions(cell, parent) {
+                let jump = if d_row != 0 && d_col != 0 {
+                    self.jump_diagonal(cell, d_row, d_col, goal)
+                } else {
+                    self.jump_straight(cell, d_row, d_col, goal)
+                };
+                let Some(next) = jump else {
+                    continue;
+                };
+                let next_idx = board.index_of(next.row, next.col);
+                let next_cost = cost + octile(cell, next);
+                if next_cost < costs[next_idx] {
+                    costs[next_idx] = next_cost;
+                    parents[next_idx] = idx;
+                    open.push(Reverse(OpenCell {
+                        estimate: next_cost + octile(next, goal),
+                        cost: next_cost,
+                        idx: next_idx,
+                    }));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns true if [row, col] is on the board and not blocked.
+    fn open(&self, row: isize, col: isize) -> bool {
+        match (usize::try_from(row), usize::try_from(col)) {
+            (Ok(row), Ok(col)) if row < self.board.n_rows() && col < self.board.n_cols() => {
+                !self.board.get(row, col)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the first jump point found by moving straight from `cell`, if any.
+    fn jump_straight(&self, cell: Coord, d_row: isize, d_col: isize, goal: Coord) -> Option<Coord> {
+        if d_row == 0 {
+            let goal = (goal.row == cell.row).then_some(goal.col);
+            scan_line(
+                self.board.board(),
+                self.board.n_cols(),
+                cell.row,
+                cell.col,
+                d_col > 0,
+                goal,
+            )
+            .map(|col| Coord::new(cell.row, col))
+        } else {
+            let goal = (goal.col == cell.col).then_some(goal.row);
+            scan_line(
+                self.transposed.board(),
+                self.board.n_rows(),
+                cell.col,
+                cell.row,
+                d_row > 0,
+                goal,
+            )
+            .map(|row| Coord::new(row, cell.col))
+        }
+    }
+
+    /// Returns the first jump point found by moving diagonally from `cell`, if any.
+    /// A cell on the diagonal is a jump point if it is the goal, or if a straight jump
+    /// from it along either part of the diagonal finds one.
+    fn jump_diagonal(&self, cell: Coord, d_row: isize, d_col: isize, goal: Coord) -> Option<Coord> {
+        let (mut row, mut col) = (cell.row as isize, cell.col as isize);
+        loop {
+            // Corners may not be cut, so both sides of the step must be open
+            if !self.open(row + d_row, col) || !self.open(row, col + d_col) {
+                return None;
+            }
+            row += d_row;
+            col += d_col;
+            if !self.open(row, col) {
+                return None;
+            }
+            let here = Coord::new(row as usize, col as usize);
+            if here == goal
+                || self.jump_straight(here, 0, d_col, goal).is_some()
+                || self.jump_straight(here, d_row, 0, goal).is_some()
+            {
+                return Some(here);
+            }
+        }
+    }
+
+    /// Returns the directions worth searching from `cell`, having arrived from
+    /// `parent`. Without a parent every direction is searched.
+    fn directions(&self, cell: Coord, parent: Option<Coord>) -> Vec<(isize, isize)> {
+        let (row, col) = (cell.row as isize, cell.col as isize);
+        let open = |d_row: isize, d_col: isize| self.open(row + d_row, col + d_col);
+        let Some(parent) = parent else {
+            return Connectivity::Eight
+                .offsets()
+                .iter()
+                .copied()
+                .filter(|&(d_row, d_col)| open(d_row, d_col) && open(d_row, 0) && open(0, d_col))
+                .collect();
+        };
+
+        let d_row = (row - parent.row as isize).signum();
+        let d_col = (col - parent.col as isize).signum();
+        let mut directions = Vec::new();
+        if d_row != 0 && d_col != 0 {
+            directions.extend([(d_row, 0), (0, d_col), (d_row, d_col)]);
+        } else if d_row == 0 {
+            directions.extend([(0, d_col), (-1, d_col), (1, d_col), (-1, 0), (1, 0)]);
+        } else {
+            directions.extend([(d_row, 0), (d_row, -1), (d_row, 1), (0, -1), (0, 1)]);
+        }
+        directions.retain(|&(d_row, d_col)| open(d_row, d_col) && open(d_row, 0) && open(0, d_col));
+        directions
+    }
+}
+
+/// In a row-major obstacle mask of `n_cols` columns, scans along `row` from `col`
+/// (exclusive) towards higher columns if `forward`, or lower ones otherwise. Returns
+/// the first column that is the `goal` column or that has a forced neighbor: an open
+/// cell in an adjacent row whose predecessor along the scan is blocked. Returns `None`
+/// if a blocked cell or the edge comes first.
+///
+/// Each of the three rows is searched a word at a time for the first blocked cell and
+/// the first falling edge.
+fn scan_line(
+    grid: &BitSlice,
+    n_cols: usize,
+    row: usize,
+    col: usize,
+    forward: bool,
+    goal: Option<usize>,
+) -> Option<usize> {
+    let n_rows = grid.len() / n_cols;
+    let line = |r: usize| &grid[r * n_cols..(r + 1) * n_cols];
+    let current = line(row);
+    let sides = [row.checked_sub(1), Some(row + 1).filter(|&r| r < n_rows)];
+
+    if forward {
+        let start = col + 1;
+        let end = start + current.get(start..)?.first_one().unwrap_or(n_cols - start);
+        if end == start {
+            return None;
+        }
+        let mut found = goal.filter(|g| (start..end).contains(g));
+        for side in sides.into_iter().flatten().map(line) {
+            // A falling edge is an unset bit after the first set one from `start - 1`
+            let Some(blocked) = side[start - 1..end].first_one() else {
+                continue;
+            };
+            let blocked = start - 1 + blocked;
+            if let Some(open) = side[blocked + 1..end].first_zero() {
+                found = Some(found.map_or(blocked + 1 + open, |f| f.min(blocked + 1 + open)));
+            }
+        }
+        found
+    } else {
+        let last = col.checked_sub(1)?;
+        let first = current[..=last].last_one().map_or(0, |blocked| blocked + 1);
+        if first > last {
+            return None;
+        }
+        let mut found = goal.filter(|g| (first..=last).contains(g));
+        for side in sides.into_iter().flatten().map(line) {
+            let Some(blocked) = side[first + 1..=last + 1].last_one() else {
+                continue;
+            };
+            let blocked = first + 1 + blocked;
+            if let Some(open) = side[first..blocked].last_zero() {
+                found = Some(found.map_or(first + open, |f| f.max(first + open)));
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitboarddyn::BitBoardDyn, bitboardstatic::BitBoardStatic, parse::picture};
+    use rstest::rstest;
+
+    /// A maze where `#` is a wall and `.` is open. [`picture`] reads it as an obstacle
+    /// mask, for the weighted searches.
+    const MAZE: &str = "
+        ..#.....
+        .##.###.
+        ....#...
+        ###.#.##
+        .#..#...
+    ";
+
+    /// The maze as a mask of walkable cells, for the breadth-first searches.
+    fn maze() -> BitBoardDyn {
+        picture(MAZE).not()
+    }
+
+    /// Checks that each step of `path` moves to a walkable neighbor.
+    fn assert_walkable(board: &BitBoardDyn, path: &[Coord], connectivity: Connectivity) {
+        for pair in path.windows(2) {
+            assert!(board.get(pair[1].row, pair[1].col), "{} is a wall", pair[1]);
+            let is_neighbor = connectivity.offsets().iter().any(|&(d_row, d_col)| {
+                board.neighbor_of(pair[0].row, pair[0].col, d_row, d_col)
+                    == Some((pair[1].row, pair[1].col))
+            });
+            assert!(is_neighbor, "{} to {} is not a step", pair[0], pair[1]);
+        }
+    }
+
+    #[rstest]
+    #[case(Connectivity::Four, Some(19))]
+    #[case(Connectivity::Eight, Some(12))]
+    fn maze_shortest_path(#[case] connectivity: Connectivity, #[case] expected: Option<usize>) {
+        let board = maze();
+        let start = Coord::new(0, 0);
+        let goal = Coord::new(4, 7);
+        let path = board.shortest_path(start, goal, connectivity).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(Some(path.len() - 1), expected);
+        assert_walkable(&board, &path, connectivity);
+
+        let distances = board.distance_map(start, connectivity);
+        assert_eq!(distances[board.index_of(4, 7)], expected.map(|d| d as u32));
+    }
+
+    #[test]
+    fn distance_map_marks_walls_and_islands() {
+        let board = maze();
+        let distances = board.distance_map(Coord::new(0, 0), Connectivity::Four);
+        assert_eq!(distances[board.index_of(0, 0)], Some(0));
+        assert_eq!(distances[board.index_of(2, 0)], Some(2));
+        assert_eq!(distances[board.index_of(0, 2)], None);
+        assert_eq!(distances[board.index_of(4, 3)], Some(7));
+        // The bottom left corner is walled off
+        assert_eq!(distances[board.index_of(4, 0)], None);
+        assert_eq!(
+            board.shortest_path(Coord::new(0, 0), Coord::new(4, 0), Connectivity::Four),
+            None
+        );
+    }
+
+    #[test]
+    fn path_to_self_and_from_wall() {
+        let board = maze();
+        let start = Coord::new(2, 2);
+        assert_eq!(
+            board.shortest_path(start, start, Connectivity::Eight),
+            Some(vec![start])
+        );
+        let wall = Coord::new(0, 2);
+        assert_eq!(board.shortest_path(wall, start, Connectivity::Four), None);
+        assert!(
+            board
+                .distance_map(wall, Connectivity::Four)
+                .iter()
+                .all(Option::is_none)
+        );
+    }
+
+    #[test]
+    fn path_wraps_around_torus() {
+        let mut board = BitBoardStatic::<1>::new(3, 8);
+        board.set_row(1, true);
+        let start = Coord::new(1, 0);
+        let goal = Coord::new(1, 7);
+        assert_eq!(
+            board
+                .shortest_path(start, goal, Connectivity::Four)
+                .unwrap()
+                .len(),
+            8
+        );
+
+        board.set_boundary(Boundary::Wrap);
+        assert_eq!(
+            board.shortest_path(start, goal, Connectivity::Four),
+            Some(vec![start, goal])
+        );
+    }
+
+    #[test]
+    fn distance_map_matches_per_cell_bfs() {
+        // A wide board with walls every few cells, checked against a plain queue BFS
+        let mut board = BitBoardDyn::new(9, 100);
+        board.fill(true);
+        for idx in (0..900).filter(|i| i % 7 == 3 || i % 11 == 5) {
+            board.board_mut().set(idx, false);
+        }
+        let start = Coord::new(4, 50);
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let mut expected = vec![None; 900];
+            let mut queue = std::collections::VecDeque::from([(start, 0)]);
+            expected[board.index_of(start.row, start.col)] = Some(0);
+            while let Some((cell, d)) = queue.pop_front() {
+                for &(d_row, d_col) in connectivity.offsets() {
+                    if let Some((row, col)) = board.neighbor_of(cell.row, cell.col, d_row, d_col) {
+                        let idx = board.index_of(row, col);
+                        if board.get(row, col) && expected[idx].is_none() {
+                            expected[idx] = Some(d + 1);
+                            queue.push_back((Coord::new(row, col), d + 1));
+                        }
+                    }
+                }
+            }
+            assert_eq!(board.distance_map(start, connectivity), expected);
+        }
+    }
+
+    /// Checks that `path` is made of allowed steps across the unset cells of `board`,
+    /// and that its cost adds up.
+    fn assert_valid_path(board: &BitBoardDyn, path: &Path, diagonal: DiagonalMovement) {
+        let mut cost = 0.0;
+        for pair in path.cells.windows(2) {
+            let (next, step_cost) = moves(board, pair[0], diagonal)
+                .find(|&(next, _)| next == pair[1])
+                .unwrap_or_else(|| panic!("{} to {} is not a step", pair[0], pair[1]));
+            assert_eq!(next, pair[1]);
+            cost += step_cost;
+        }
+        assert!((cost - path.cost).abs() < 1e-9);
+    }
+
+    #[test]
+    fn astar_straight_matches_bfs() {
+        let board = picture(MAZE);
+        let start = Coord::new(0, 0);
+        let goal = Coord::new(4, 7);
+        for heuristic in [
+            Heuristic::Manhattan,
+            Heuristic::Octile,
+            Heuristic::Chebyshev,
+        ] {
+            let path = board
+                .astar(start, goal, heuristic, DiagonalMovement::Never)
+                .unwrap();
+            assert_eq!(path.cost, 19.0);
+            assert_eq!(path.cells.first(), Some(&start));
+            assert_eq!(path.cells.last(), Some(&goal));
+            assert_valid_path(&board, &path, DiagonalMovement::Never);
+        }
+    }
+
+    #[rstest]
+    #[case(DiagonalMovement::Always, 2.0 + 4.0 * SQRT_2)]
+    #[case(DiagonalMovement::IfAtMostOneBlocked, 2.0 + 6.0 * SQRT_2)]
+    #[case(DiagonalMovement::IfNoneBlocked, 6.0 + 4.0 * SQRT_2)]
+    #[case(DiagonalMovement::Never, 14.0)]
+    fn astar_corner_cutting(#[case] diagonal: DiagonalMovement, #[case] expected: f64) {
+        // The wall can only be crossed diagonally: squeezing between (1, 2) and (2, 3),
+        // or between (2, 3) and (3, 2), or by going around through the bottom row
+        let board = picture(
+            "
+            ..#....
+            ..#....
+            ...#...
+            ..#....
+            .......
+        ",
+        );
+        let path = board
+            .astar(
+                Coord::new(0, 0),
+                Coord::new(0, 6),
+                Heuristic::Octile,
+                diagonal,
+            )
+            .unwrap();
+        assert!((path.cost - expected).abs() < 1e-9, "{}", path.cost);
+        assert_valid_path(&board, &path, diagonal);
+    }
+
+    #[test]
+    fn astar_octile_and_chebyshev_agree() {
+        // Scatter walls over about a quarter of the cells
+        let mut board = BitBoardDyn::new(20, 70);
+        for idx in 0..1400 {
+            board.board_mut().set(idx, idx * 2_654_435_761 % 1000 < 250);
+        }
+        let start = Coord::new(0, 0);
+        let goal = Coord::new(19, 66);
+        board.set(start.row, start.col, false);
+        board.set(goal.row, goal.col, false);
+        for diagonal in [
+            DiagonalMovement::Always,
+            DiagonalMovement::IfAtMostOneBlocked,
+            DiagonalMovement::IfNoneBlocked,
+        ] {
+            let octile = board
+                .astar(start, goal, Heuristic::Octile, diagonal)
+                .unwrap();
+            let chebyshev = board
+                .astar(start, goal, Heuristic::Chebyshev, diagonal)
+                .unwrap();
+            assert!((octile.cost - chebyshev.cost).abs() < 1e-9);
+            assert_valid_path(&board, &octile, diagonal);
+        }
+
+        let straight = board
+            .astar(start, goal, Heuristic::Manhattan, DiagonalMovement::Never)
+            .unwrap();
+        let steps = board.not().distance_map(start, Connectivity::Four)[board.index_of(19, 66)];
+        assert_eq!(Some(straight.cost as u32), steps);
+    }
+
+    #[test]
+    fn astar_unreachable_and_walls() {
+        let board = picture(MAZE);
+        let start = Coord::new(0, 0);
+        for diagonal in [DiagonalMovement::Never, DiagonalMovement::Always] {
+            assert_eq!(
+                board.astar(start, Coord::new(4, 0), Heuristic::Octile, diagonal),
+                None
+            );
+        }
+        assert_eq!(
+            board.astar(
+                start,
+                Coord::new(0, 2),
+                Heuristic::Manhattan,
+                DiagonalMovement::Never
+            ),
+            None
+        );
+        assert_eq!(
+            board.astar(start, start, Heuristic::Manhattan, DiagonalMovement::Never),
+            Some(Path {
+                cells: vec![start],
+                cost: 0.0
+            })
+        );
+    }
+
+    #[test]
+    fn astar_wraps_around_torus() {
+        let mut board = BitBoardStatic::<1>::new(4, 9);
+        board.set_col(4, true);
+        board.set_boundary(Boundary::Wrap);
+        let path = board
+            .astar(
+                Coord::new(0, 2),
+                Coord::new(3, 6),
+                Heuristic::Octile,
+                DiagonalMovement::Always,
+            )
+            .unwrap();
+        // Around the back: one diagonal up across the top edge, then west across the
+        // side edge
+        assert!((path.cost - (4.0 + SQRT_2)).abs() < 1e-9, "{}", path.cost);
+    }
+
+    /// An obstacle mask with walls over about `percent` of the cells, picked by hashing
+    /// the index.
+    fn scattered_walls(n_rows: usize, n_cols: usize, percent: usize, seed: usize) -> BitBoardDyn {
+        let mut board = BitBoardDyn::new(n_rows, n_cols);
+        for idx in 0..n_rows * n_cols {
+            let hash = (idx + seed).wrapping_mul(2_654_435_761) % 100;
+            board.board_mut().set(idx, hash < percent);
+        }
+        board
+    }
+
+    #[rstest]
+    #[case(1, 1, 0, 0)]
+    #[case(12, 90, 10, 1)]
+    #[case(12, 90, 25, 2)]
+    #[case(30, 70, 30, 3)]
+    #[case(64, 64, 20, 4)]
+    #[case(7, 130, 35, 5)]
+    fn jump_point_search_matches_astar(
+        #[case] n_rows: usize,
+        #[case] n_cols: usize,
+        #[case] percent: usize,
+        #[case] seed: usize,
+    ) {
+        let board = scattered_walls(n_rows, n_cols, percent, seed);
+        let grid = JumpGrid::new(&board);
+        let cells: Vec<_> = board.iter_zeros().map(Coord::from).collect();
+        // Search between a spread of open cells
+        let step = (cells.len() / 6).max(1);
+        for &start in cells.iter().step_by(step) {
+            for &goal in cells.iter().rev().step_by(step) {
+                let expected = board.astar(
+                    start,
+                    goal,
+                    Heuristic::Octile,
+                    DiagonalMovement::IfNoneBlocked,
+                );
+                let found = grid.search(start, goal);
+                match (&expected, &found) {
+                    (Some(expected), Some(found)) => {
+                        assert!(
+                            (expected.cost - found.cost).abs() < 1e-9,
+                            "{start} to {goal}: {} vs {}",
+                            expected.cost,
+                            found.cost
+                        );
+                        assert_eq!(found.cells.first(), Some(&start));
+                        assert_eq!(found.cells.last(), Some(&goal));
+                        assert_valid_path(&board, found, DiagonalMovement::IfNoneBlocked);
+                    }
+                    (None, None) => {}
+                    _ => panic!("{start} to {goal}: {expected:?} vs {found:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn jump_point_search_open_board() {
+        let board = BitBoardDyn::new(50, 200);
+        let path = board
+            .jump_point_search(Coord::new(3, 2), Coord::new(40, 190))
+            .unwrap();
+        assert!((path.cost - (151.0 + 37.0 * SQRT_2)).abs() < 1e-9);
+        assert_eq!(path.cells.len(), 189);
+        assert_valid_path(&board, &path, DiagonalMovement::IfNoneBlocked);
+    }
+
+    #[test]
+    fn jump_point_search_maze() {
+        let board = picture(MAZE);
+        let start = Coord::new(0, 0);
+        let path = board.jump_point_search(start, Coord::new(4, 7)).unwrap();
+        let expected = board
+            .astar(
+                start,
+                Coord::new(4, 7),
+                Heuristic::Octile,
+                DiagonalMovement::IfNoneBlocked,
+            )
+            .unwrap();
+        assert!((path.cost - expected.cost).abs() < 1e-9);
+        assert_valid_path(&board, &path, DiagonalMovement::IfNoneBlocked);
+        assert_eq!(board.jump_point_search(start, Coord::new(4, 0)), None);
+        assert_eq!(board.jump_point_search(start, Coord::new(0, 2)), None);
+    }
+
+    #[test]
+    fn jump_point_search_wraps_with_astar() {
+        let mut board = BitBoardStatic::<1>::new(4, 9);
+        board.set_col(4, true);
+        board.set_boundary(Boundary::Wrap);
+        let path = board
+            .jump_point_search(Coord::new(1, 2), Coord::new(1, 6))
+            .unwrap();
+        assert_eq!(path.cost, 5.0);
+    }
+}
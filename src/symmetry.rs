@@ -0,0 +1,118 @@
+//! The eight symmetries of a rectangle's grid: the rotations and reflections of the
+//! dihedral group D4.
+
+use crate::bitboard::BitBoard;
+
+/// One of the eight ways to rotate or reflect a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Leave the board as it is.
+    Identity,
+
+    /// Rotate a quarter turn clockwise.
+    Rotate90,
+
+    /// Rotate half a turn.
+    Rotate180,
+
+    /// Rotate a quarter turn counter-clockwise.
+    Rotate270,
+
+    /// Mirror left to right.
+    FlipHorizontal,
+
+    /// Mirror top to bottom.
+    FlipVertical,
+
+    /// Mirror across the main diagonal, from the top left to the bottom right.
+    Transpose,
+
+    /// Mirror across the anti-diagonal, from the top right to the bottom left.
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// All eight symmetries, identity first.
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Returns `board` transformed by this symmetry.
+    #[must_use]
+    pub fn apply<B: BitBoard>(self, board: &B) -> B {
+        match self {
+            Symmetry::Identity => board.clone(),
+            Symmetry::Rotate90 => board.rotate_90(),
+            Symmetry::Rotate180 => board.rotate_180(),
+            Symmetry::Rotate270 => board.rotate_270(),
+            Symmetry::FlipHorizontal => board.flip_horizontal(),
+            Symmetry::FlipVertical => board.flip_vertical(),
+            Symmetry::Transpose => board.transpose(),
+            Symmetry::AntiTranspose => board.rotate_180().transpose(),
+        }
+    }
+
+    /// Returns the symmetry that undoes this one.
+    #[must_use]
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            // Every other symmetry is its own inverse
+            other => other,
+        }
+    }
+
+    /// Returns true if this symmetry swaps the number of rows and columns.
+    #[must_use]
+    pub fn swaps_dims(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboarddyn::BitBoardDyn;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Symmetry::Identity)]
+    #[case(Symmetry::Rotate90)]
+    #[case(Symmetry::Rotate180)]
+    #[case(Symmetry::Rotate270)]
+    #[case(Symmetry::FlipHorizontal)]
+    #[case(Symmetry::FlipVertical)]
+    #[case(Symmetry::Transpose)]
+    #[case(Symmetry::AntiTranspose)]
+    fn inverse_undoes_apply(#[case] symmetry: Symmetry) {
+        let bb = BitBoardDyn::from_coords(2, 3, [(0, 0), (0, 1), (1, 2)]).unwrap();
+        let moved = symmetry.apply(&bb);
+        assert_eq!(
+            (moved.n_rows(), moved.n_cols()) == (3, 2),
+            symmetry.swaps_dims()
+        );
+        assert_eq!(symmetry.inverse().apply(&moved), bb);
+    }
+
+    #[test]
+    fn anti_transpose_moves_corners() {
+        // [row, col] moves to [n_cols - 1 - col, n_rows - 1 - row]
+        let bb = BitBoardDyn::from_coords(2, 3, [(0, 0), (1, 1)]).unwrap();
+        let moved = Symmetry::AntiTranspose.apply(&bb);
+        assert_eq!(moved.iter_ones().collect::<Vec<_>>(), vec![(1, 0), (2, 1)]);
+    }
+}